@@ -1,13 +1,13 @@
 pub mod migrator;
 pub use chela_derive::*;
-use chela_query::builder::{CreateBuilder, InsertBuilder, QueryBuilder};
-use chela_query::create::{ColumnDef, ColumnOptionDef, DataType};
+use chela_query::builder::{
+    create_schema, insert_into, insert_into_in, select_table, select_table_in, InsertBuilder,
+    QueryBuilder,
+};
+pub use chela_query::create::ReferentialAction;
+use chela_query::create::{ColumnOptionDef, DataType};
 use chela_query::statement::Statement;
-use futures::future::join_all;
 use migrator::{Migrations, Migrator};
-use std::any::Any;
-use std::rc::Rc;
-use std::{any::TypeId, collections::HashMap};
 
 use async_trait::async_trait;
 use tokio_postgres::Client;
@@ -17,13 +17,19 @@ pub trait QueryRunner: Builder {
     type CreateInput;
     async fn load(&self, client: &Client) -> Vec<Self::Output>;
 
-    fn create(&self, input: Self::CreateInput); //->Self::Output;//client: &Client
-//delete
-//update
+    /// Inserts `input` and reads the stored row back through RETURNING
+    async fn create(&self, client: &Client, input: Self::CreateInput) -> Self::Output;
+    //delete
+    //update
 }
 
 pub trait Repository {
     fn entity(&self) -> Entity;
+
+    /// INSERT of every column that updates the existing row on a primary key conflict
+    fn upsert(&self) -> InsertBuilder {
+        self.entity().upsert()
+    }
     // fn as_any(&self) -> &dyn Any;
 }
 
@@ -31,7 +37,6 @@ pub trait ToEntity {
     fn to_entity() -> Entity;
 }
 
-
 //TODO: QueryDebugger
 pub trait Builder {
     fn select(&self) -> QueryBuilder;
@@ -45,20 +50,59 @@ pub trait PreloadBuilder<'a> {
 #[derive(Debug, Clone)]
 pub struct Entity {
     pub table_name: String,
+    /// Schema the table lives in, `public` when None
+    pub schema: Option<String>,
     pub struct_name: String, // the struct to be parse, like the User struct above.
     pub columns: Vec<Column>, // the struct's fields
     pub has_many: Vec<HasMany>,
     pub belongs_to: Vec<BelongsTo>,
+    pub indexes: Vec<Index>,
 }
 
 impl Entity {
-    fn has_many(self) -> bool {
+    pub fn has_many(self) -> bool {
         !self.has_many.is_empty()
     }
 
-    fn belongs_to(&self) -> bool {
+    pub fn belongs_to(&self) -> bool {
         !self.belongs_to.is_empty()
     }
+
+    /// SELECT with the entity's columns listed explicitly, in declaration order
+    pub fn select(&self) -> QueryBuilder {
+        let query = match &self.schema {
+            Some(schema) => select_table_in(schema.to_string(), self.table_name.to_string()),
+            None => select_table(self.table_name.to_string()),
+        };
+        query.columns(self.columns.iter().map(|column| column.name.as_str()))
+    }
+
+    /// INSERT of every column, ON CONFLICT on the primary key it overwrites the
+    /// other columns, so writing the same rows twice leaves one copy of each.
+    /// Without a primary key there is nothing to update by, colliding rows are
+    /// skipped with DO NOTHING.
+    pub fn upsert(&self) -> InsertBuilder {
+        let insert = match &self.schema {
+            Some(schema) => insert_into_in(schema.to_string(), self.table_name.to_string()),
+            None => insert_into(self.table_name.to_string()),
+        };
+        let (primary, others): (Vec<&Column>, Vec<&Column>) =
+            self.columns.iter().partition(|column| column.is_primary());
+        let names = |columns: Vec<&Column>| -> Vec<String> {
+            columns
+                .iter()
+                .map(|column| column.name.to_string())
+                .collect()
+        };
+        let insert = insert.columns(names(self.columns.iter().collect()));
+        if primary.is_empty() {
+            return insert.do_nothing();
+        }
+        insert
+            .on_conflict(names(primary))
+            .do_update_set_excluded(names(others))
+            .end()
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -83,6 +127,20 @@ pub struct BelongsTo {
     pub foreign_key: String,
     pub struct_name: String,
     pub table_name: String,
+    /// Schema of the referenced table, `public` when None
+    pub schema: Option<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+    /// DEFERRABLE INITIALLY DEFERRED
+    pub deferrable: bool,
+}
+
+/// Index declared with `#[index]` or `#[chela(index(...))]`
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
 }
 
 #[derive(Clone)]
@@ -111,12 +169,37 @@ impl Chela {
         // let repositories = Vec::new(); //HashMap::new();
         let schema = Schema::new(entities);
 
-        let statements: Vec<Statement> = schema
-            .clone()
+        // schemas go first, so the tables have somewhere to be created
+        let mut schemas: Vec<&String> = vec![];
+        for name in schema
             .entities
+            .iter()
+            .filter_map(|entity| entity.schema.as_ref())
+        {
+            if !schemas.contains(&name) {
+                schemas.push(name);
+            }
+        }
+        let mut statements: Vec<Statement> = schemas
             .into_iter()
-            .map(|entity| Statement::CreateStmt(entity.create_table()))
+            .map(|name| {
+                Statement::CreateSchemaStmt(create_schema(name.to_string()).if_not_exists().build())
+            })
             .collect();
+        statements.extend(
+            schema
+                .clone()
+                .entities
+                .into_iter()
+                .map(|entity| Statement::CreateStmt(entity.create_table())),
+        );
+        // indexes go after every table, so the batch reads tables first
+        statements.extend(schema.entities.iter().flat_map(|entity| {
+            entity
+                .indexes
+                .iter()
+                .map(|index| Statement::CreateIndexStmt(entity.create_index(index)))
+        }));
         let migrations = Migrations(statements);
         Chela {
             schema,
@@ -133,6 +216,19 @@ impl Chela {
         self.migrations
     }
 
+    /// Drops the tables the migrations created, in reverse order so that
+    /// referencing tables go before the tables they refer to
+    pub fn teardown(&self) -> Migrations {
+        let statements = self
+            .schema
+            .entities
+            .iter()
+            .rev()
+            .map(|entity| Statement::DropStmt(entity.drop_table()))
+            .collect();
+        Migrations(statements)
+    }
+
     // pub fn add_repo(&mut self, repo: Rc<dyn Repository>) {
     //     self.repositories.push(repo) //.insert(repo.type_id(), repo);
     // }
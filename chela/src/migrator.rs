@@ -2,36 +2,50 @@ use std::fmt::Display;
 
 use async_trait::async_trait;
 use chela_query::{
-    builder::create_table,
-    create::{ColumnDef, CreateStmt},
+    alter::{AlterColumnOperation, AlterTableStmt},
+    builder::{
+        alter_table, alter_table_in, create_index, create_index_in, create_table, create_table_in,
+        drop_table, drop_table_in, foreign_key, foreign_key_in, AlterBuilder, DropBuilder,
+    },
+    create::{ColumnDef, CreateStmt, TableConstraint},
+    drop::{DropStmt, ObjectType},
+    index::CreateIndexStmt,
     query::Ident,
 };
-use futures::future::join_all;
 use tokio_postgres::Client;
 
-use crate::{Column, Entity, Statement};
+use crate::{BelongsTo, Column, Entity, Index, Statement};
 
 pub trait Migrator {
+    /// CREATE TABLE IF NOT EXISTS, so running the migrations twice does not fail
     fn create_table(self) -> CreateStmt;
 
-    //fn drop_table
+    /// DROP TABLE IF EXISTS, so tearing down twice does not fail
+    fn drop_table(&self) -> DropStmt;
     //fn has_table
     //fn rename_table
     //fn get_tables
 
-    //fn add_column
+    fn add_column(&self, column: Column) -> AlterTableStmt;
+    fn drop_column(&self, column_name: &str) -> AlterTableStmt;
+    fn rename_column(&self, old_name: &str, new_name: &str) -> AlterTableStmt;
+    /// Applies each of `operations` to the column, nothing else is changed
+    fn alter_column(
+        &self,
+        column_name: &str,
+        operations: Vec<AlterColumnOperation>,
+    ) -> AlterTableStmt;
     //fn has_column
-    //fn drop_column
-    //fn rename_column
-    //fn alter_column
     //fn migrate_column
 
-    //fn create_constraint
-    //fn drop_constraint
+    fn create_constraint(&self, belongs_to: &BelongsTo) -> AlterTableStmt;
+    fn drop_constraint(&self, constraint_name: &str) -> AlterTableStmt;
     //fn has_constraint
 
-    //fn create_index
-    //fn drop_index
+    /// CREATE INDEX IF NOT EXISTS, like create_table
+    fn create_index(&self, index: &Index) -> CreateIndexStmt;
+    /// DROP INDEX IF EXISTS, in the entity's schema
+    fn drop_index(&self, index_name: &str) -> DropStmt;
     //fn rename_index
     //fn has_index
 }
@@ -44,7 +58,7 @@ pub trait MigrationRunner {
 impl From<Column> for ColumnDef {
     fn from(column: Column) -> Self {
         ColumnDef {
-            name: Ident { value: column.name },
+            name: Ident::new(column.name),
             data_type: column.data_type,
             options: column.options,
         }
@@ -53,37 +67,176 @@ impl From<Column> for ColumnDef {
 
 impl Column {
     pub fn is_primary(&self) -> bool {
-        let option: Vec<bool> = self
-            .options
-            .clone()
-            .into_iter()
-            .filter_map(|option_def| option_def.option.is_primary())
-            .collect();
-        let is_unique = option.into_iter().next().unwrap();
-        is_unique
+        self.options
+            .iter()
+            .any(|option_def| option_def.option.is_primary() == Some(true))
+    }
+}
+impl BelongsTo {
+    /// FOREIGN KEY with the ON DELETE / ON UPDATE actions from `#[belongs_to]`
+    pub fn constraint(&self) -> TableConstraint {
+        let builder = match &self.schema {
+            Some(schema) => foreign_key_in(
+                self.constraint_name.to_string(),
+                self.column_name.to_string(),
+                schema.to_string(),
+                self.table_name.to_string(),
+                self.foreign_key.to_string(),
+            ),
+            None => foreign_key(
+                self.constraint_name.to_string(),
+                self.column_name.to_string(),
+                self.table_name.to_string(),
+                self.foreign_key.to_string(),
+            ),
+        };
+        let builder = match self.on_delete {
+            Some(action) => builder.on_delete(action),
+            None => builder,
+        };
+        let builder = match self.on_update {
+            Some(action) => builder.on_update(action),
+            None => builder,
+        };
+        if self.deferrable {
+            builder.deferrable().build()
+        } else {
+            builder.build()
+        }
     }
 }
+
+impl Entity {
+    fn alter_table(&self) -> AlterBuilder {
+        match &self.schema {
+            Some(schema) => alter_table_in(schema.to_string(), self.table_name.to_string()),
+            None => alter_table(self.table_name.to_string()),
+        }
+    }
+}
+
 impl Migrator for Entity {
+    /// Several primary key columns become one table level PRIMARY KEY
     fn create_table(self) -> CreateStmt {
+        let primary_keys = self
+            .columns
+            .iter()
+            .filter(|column| column.is_primary())
+            .map(|column| column.name.to_string())
+            .collect::<Vec<String>>();
+        let composite = primary_keys.len() > 1;
         let columns = self
             .columns
             .clone()
             .into_iter()
-            .map(|c| ColumnDef::from(c))
+            .map(|mut column| {
+                if composite {
+                    column
+                        .options
+                        .retain(|option_def| option_def.option.is_primary() != Some(true));
+                }
+                ColumnDef::from(column)
+            })
             .collect::<Vec<ColumnDef>>();
-        let stmt = create_table(self.table_name.to_string(), columns);
-        if !self.belongs_to.clone().is_empty() {
-            stmt.foreign_key_constraint(
-                self.belongs_to[0].constraint_name.to_string(), //TODO: unhardcode this
-                self.belongs_to[0].column_name.to_string(),
-                self.belongs_to[0].table_name.to_string(),
-                self.belongs_to[0].foreign_key.to_string(),
-            )
+        let stmt = match &self.schema {
+            Some(schema) => {
+                create_table_in(schema.to_string(), self.table_name.to_string(), columns)
+            }
+            None => create_table(self.table_name.to_string(), columns),
+        }
+        .if_not_exists();
+        let stmt = if composite {
+            stmt.primary_key_constraint(primary_keys)
+        } else {
+            stmt
+        };
+        self.belongs_to
+            .iter()
+            .fold(stmt, |stmt, belongs_to| {
+                stmt.constraint(belongs_to.constraint())
+            })
+            .build()
+    }
+
+    fn drop_table(&self) -> DropStmt {
+        match &self.schema {
+            Some(schema) => drop_table_in(schema.to_string(), self.table_name.to_string()),
+            None => drop_table(self.table_name.to_string()),
+        }
+        .if_exists()
+        .build()
+    }
+
+    fn add_column(&self, column: Column) -> AlterTableStmt {
+        self.alter_table()
+            .add_column(column.name, column.data_type, column.options)
+            .build()
+    }
+
+    fn drop_column(&self, column_name: &str) -> AlterTableStmt {
+        self.alter_table()
+            .drop_column(column_name.to_string())
+            .build()
+    }
+
+    fn rename_column(&self, old_name: &str, new_name: &str) -> AlterTableStmt {
+        self.alter_table()
+            .rename_column(old_name.to_string(), new_name.to_string())
+            .build()
+    }
+
+    fn alter_column(
+        &self,
+        column_name: &str,
+        operations: Vec<AlterColumnOperation>,
+    ) -> AlterTableStmt {
+        operations
+            .into_iter()
+            .fold(self.alter_table(), |alter, op| {
+                alter.alter_column(column_name.to_string(), op)
+            })
+            .build()
+    }
+
+    fn create_constraint(&self, belongs_to: &BelongsTo) -> AlterTableStmt {
+        self.alter_table()
+            .add_constraint(belongs_to.constraint())
             .build()
+    }
+
+    fn drop_constraint(&self, constraint_name: &str) -> AlterTableStmt {
+        self.alter_table()
+            .drop_constraint(constraint_name.to_string())
+            .build()
+    }
+
+    fn create_index(&self, index: &Index) -> CreateIndexStmt {
+        let builder = match &self.schema {
+            Some(schema) => create_index_in(
+                index.name.to_string(),
+                schema.to_string(),
+                self.table_name.to_string(),
+            ),
+            None => create_index(index.name.to_string(), self.table_name.to_string()),
+        }
+        .columns(index.columns.clone())
+        .if_not_exists();
+        if index.unique {
+            builder.unique().build()
         } else {
-            stmt.build()
+            builder.build()
         }
     }
+
+    fn drop_index(&self, index_name: &str) -> DropStmt {
+        let builder = DropBuilder::new(ObjectType::Index);
+        match &self.schema {
+            Some(schema) => builder.name_in(schema.to_string(), index_name.to_string()),
+            None => builder.name(index_name.to_string()),
+        }
+        .if_exists()
+        .build()
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -91,10 +244,7 @@ pub struct Migrations(pub Vec<Statement>);
 
 impl Display for Migrations {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for statement in self.0.iter() {
-            write!(f, "{}", statement)?;
-        }
-        Ok(())
+        write!(f, "{}", Statement::to_sql_batch(&self.0))
     }
 }
 impl Migrations {
@@ -107,12 +257,15 @@ impl Migrations {
 impl MigrationRunner for Statement {
     async fn run(&self, client: &Client) {
         let statement = self.to_string();
-        let row = client.execute(&statement.to_string(), &[]).await.unwrap();
+        client.execute(&statement, &[]).await.unwrap();
     }
 }
 #[async_trait]
 impl MigrationRunner for Migrations {
+    /// One statement after the other, later statements may depend on earlier ones
     async fn run(&self, client: &Client) {
-        join_all(self.0.iter().map(|statement| statement.run(&client))).await;
+        for statement in &self.0 {
+            statement.run(client).await;
+        }
     }
 }
@@ -1,14 +1,12 @@
 // use chela_query::runner::QueryRunner;
 use proc_macro2::TokenStream;
-use proc_macro_error::abort_call_site;
+use proc_macro_error::{abort, abort_call_site, proc_macro_error};
 use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
-use syn::{
-    parse_macro_input, parse_quote, DeriveInput, Ident, Lit, LitStr, Meta, MetaNameValue,
-    NestedMeta, Type,
-};
+use syn::{parse_macro_input, DeriveInput, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Type};
 
-#[proc_macro_derive(ToEntity, attributes(has_many, primary_key, belongs_to))]
+#[proc_macro_error]
+#[proc_macro_derive(ToEntity, attributes(chela, has_many, primary_key, belongs_to, index))]
 pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(item as DeriveInput);
     let struct_name = &ast.ident;
@@ -23,11 +21,18 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
         panic!("Only support Struct")
     };
 
-    let mut keys: Vec<TokenStream> = Vec::new();
+    let mut table_name = struct_name.to_string().to_lowercase();
+    table_name.push('s');
+
+    let mut schema = None;
+    let mut index_specs = Vec::new();
+    parse_chela(&ast.attrs, &mut schema, &mut index_specs);
 
     let mut belongs_to_vec = Vec::new();
     let mut has_many_vec = Vec::new();
+    let mut has_many_entity_vec = Vec::new();
     let mut column_vec = Vec::new();
+    let mut column_names = Vec::new();
     let mut has_many_foreign_key = None;
     let mut belongs_to_foreign_key = None;
     // let mut uuid = None;
@@ -39,10 +44,12 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
             &mut has_many_foreign_key,
             &mut has_many_table_name,
             &mut has_many_vec,
+            &mut has_many_entity_vec,
         );
 
         let field_name: &syn::Ident = field.ident.as_ref().unwrap();
         let name: String = field_name.to_string();
+        parse_index(field, &name, &mut index_specs);
 
         let literal_key_str = syn::LitStr::new(&name, field.span());
         let type_name = &field.ty;
@@ -55,10 +62,10 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
             &mut belongs_to_table_name,
             &mut belongs_to_vec,
             key.clone(),
-            ty.clone(),
             // &mut column_vec,
         );
         parse_type_is_vec(field, &mut type_is_vec);
+        let column_count = column_vec.len();
         parse_primary_key(
             field,
             &mut column_vec,
@@ -66,6 +73,10 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
             ty.clone(),
             &mut type_is_vec,
         );
+        // has_many fields hold child rows and generate no column
+        if column_vec.len() > column_count {
+            column_names.push(name);
+        }
 
         // if !type_is_vec {
         //     keys.push(key);
@@ -76,16 +87,27 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
     }
 
     // let has_many_tokens = (0..has_many_vec.len()).map(syn::Index::from);
-    let i = (0..keys.len()).map(syn::Index::from);
     let repository = format_ident!("{}{}", struct_name, "Repository");
-    let mut table_name = struct_name.to_string().to_lowercase();
-    table_name.push('s');
-    let preloads = build_preloads();
+    let preloads = build_preloads(has_many_entity_vec);
     let has_many = build_vec(has_many_vec);
     let belongs_to = build_vec(belongs_to_vec);
     let columns = build_vec(column_vec);
-
-    let entity = build_entity(table_name, has_many, belongs_to, struct_name_str, columns);
+    let indexes = build_vec(
+        index_specs
+            .into_iter()
+            .map(|index_spec| build_index(&table_name, &column_names, index_spec))
+            .collect(),
+    );
+
+    let entity = build_entity(
+        table_name,
+        schema,
+        has_many,
+        belongs_to,
+        struct_name_str,
+        columns,
+        indexes,
+    );
 
     let expanded = quote! {
         impl ToEntity for #struct_name {
@@ -178,12 +200,27 @@ pub fn derive_signature(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
 
 fn parse_type_is_vec(field: &syn::Field, type_is_vec: &mut bool) {
     if let Type::Path(ref p) = field.ty {
-        *type_is_vec = p.path.segments.iter().next().unwrap().ident.to_string() == "Vec"
+        *type_is_vec = p.path.segments.iter().next().unwrap().ident == "Vec"
     } else {
         *type_is_vec = false;
     }
 }
 
+fn vec_inner_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(ref p) = ty {
+        let segment = p.path.segments.iter().next()?;
+        if segment.ident != "Vec" {
+            return None;
+        }
+        if let syn::PathArguments::AngleBracketed(ref args) = segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.iter().next() {
+                return Some(inner);
+            }
+        }
+    }
+    None
+}
+
 fn parse_primary_key(
     field: &syn::Field,
     columns: &mut Vec<TokenStream>,
@@ -192,14 +229,17 @@ fn parse_primary_key(
     type_is_vec: &mut bool,
 ) {
     let mut auto_increment = false;
+    let mut primary_key = false;
     for attribute in field
         .attrs
         .iter()
         .filter(|attribute| attribute.path.is_ident("primary_key"))
     {
+        primary_key = true;
         let meta: Meta = attribute.parse_meta().unwrap(); //.unwrap_or_abort();
 
-        const VALID_FORMAT: &str = r#"Expected `#[primary_key(auto_increment=true)]`"#;
+        const VALID_FORMAT: &str =
+            r#"Expected `#[primary_key]` or `#[primary_key(auto_increment=true)]`"#;
         if let Meta::List(meta) = meta {
             for meta in meta.nested {
                 if let NestedMeta::Meta(meta) = meta {
@@ -228,6 +268,9 @@ fn parse_primary_key(
         let column_primary_key_auto_increment =
             build_column_primary_key_auto_increment(key.clone());
         columns.push(column_primary_key_auto_increment);
+    } else if primary_key {
+        let column = build_column_primary_key(key.clone(), ty.clone());
+        columns.push(column);
     } else {
         if !*type_is_vec {
             let column = build_column_not_null(key.clone(), ty.clone());
@@ -236,11 +279,128 @@ fn parse_primary_key(
     }
 }
 
+fn parse_chela(
+    attrs: &[syn::Attribute],
+    schema: &mut Option<LitStr>,
+    index_specs: &mut Vec<IndexSpec>,
+) {
+    for attribute in attrs
+        .iter()
+        .filter(|attribute| attribute.path.is_ident("chela"))
+    {
+        let meta: Meta = attribute.parse_meta().unwrap(); //.unwrap_or_abort();
+
+        const VALID_FORMAT: &str = r#"Expected `#[chela(schema="schema name")]` or `#[chela(index(columns="a,b", unique))]`"#;
+        if let Meta::List(meta) = meta {
+            for meta in meta.nested {
+                if let NestedMeta::Meta(meta) = meta {
+                    match meta {
+                        Meta::NameValue(MetaNameValue { path, lit, .. }) => match (
+                            path.get_ident()
+                                .unwrap_or_else(|| abort_call_site!(VALID_FORMAT))
+                                .to_string()
+                                .as_str(),
+                            lit,
+                        ) {
+                            ("schema", Lit::Str(lit)) => *schema = Some(lit),
+                            _ => abort_call_site!(VALID_FORMAT),
+                        },
+                        Meta::List(meta) if meta.path.is_ident("index") => {
+                            let index_spec = parse_index_options(meta.nested, VALID_FORMAT);
+                            if index_spec.columns.is_empty() {
+                                abort_call_site!(VALID_FORMAT);
+                            }
+                            index_specs.push(index_spec);
+                        }
+
+                        _ => abort_call_site!(VALID_FORMAT),
+                    }
+                } else {
+                    abort_call_site!(VALID_FORMAT);
+                }
+            }
+        }
+    }
+}
+
+/// An index as declared by the attributes, turned into an `Index` by `build_index`
+struct IndexSpec {
+    name: Option<String>,
+    columns: Vec<String>,
+    unique: bool,
+}
+
+fn parse_index(field: &syn::Field, column_name: &str, index_specs: &mut Vec<IndexSpec>) {
+    for attribute in field
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path.is_ident("index"))
+    {
+        let meta: Meta = attribute.parse_meta().unwrap(); //.unwrap_or_abort();
+
+        const VALID_FORMAT: &str =
+            r#"Expected `#[index]` or `#[index(unique, name="index name")]`"#;
+        let mut index_spec = match meta {
+            Meta::Path(_) => IndexSpec {
+                name: None,
+                columns: vec![],
+                unique: false,
+            },
+            Meta::List(meta) => parse_index_options(meta.nested, VALID_FORMAT),
+            _ => abort_call_site!(VALID_FORMAT),
+        };
+        if !index_spec.columns.is_empty() {
+            abort_call_site!(VALID_FORMAT);
+        }
+        index_spec.columns.push(column_name.to_string());
+        index_specs.push(index_spec);
+    }
+}
+
+/// Reads `columns="a,b"`, `unique` and `name="index name"`
+fn parse_index_options(
+    nested: syn::punctuated::Punctuated<NestedMeta, syn::token::Comma>,
+    valid_format: &str,
+) -> IndexSpec {
+    let mut index_spec = IndexSpec {
+        name: None,
+        columns: vec![],
+        unique: false,
+    };
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("unique") => {
+                index_spec.unique = true
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => match (
+                path.get_ident()
+                    .unwrap_or_else(|| abort_call_site!(valid_format))
+                    .to_string()
+                    .as_str(),
+                lit,
+            ) {
+                ("name", Lit::Str(lit)) => index_spec.name = Some(lit.value()),
+                ("columns", Lit::Str(lit)) => {
+                    index_spec.columns = lit
+                        .value()
+                        .split(',')
+                        .map(|column| column.trim().to_string())
+                        .collect()
+                }
+                _ => abort_call_site!(valid_format),
+            },
+            _ => abort_call_site!(valid_format),
+        }
+    }
+    index_spec
+}
+
 fn parse_has_many(
     field: &syn::Field,
     foreign_key: &mut Option<LitStr>,
     table_name: &mut Option<LitStr>,
     has_many_vec: &mut Vec<TokenStream>,
+    has_many_entity_vec: &mut Vec<TokenStream>,
 ) {
     for attribute in field
         .attrs
@@ -278,7 +438,9 @@ fn parse_has_many(
             let struct_name = table_to_struct_name(&table_n_value);
             let struct_n = syn::LitStr::new(&struct_name, field.span());
             let has_many = build_has_many(foreign_key.clone(), struct_n, table_n);
-            has_many_vec.push(has_many)
+            has_many_vec.push(has_many);
+            let entity_ty = vec_inner_type(&field.ty).unwrap_or(&field.ty);
+            has_many_entity_vec.push(quote! { <#entity_ty as ToEntity>::to_entity() });
         }
     }
 }
@@ -289,7 +451,6 @@ fn parse_belongs_to(
     belongs_to_table_name: &mut Option<LitStr>,
     belongs_to_vec: &mut Vec<TokenStream>,
     key: TokenStream,
-    ty: TokenStream,
     // columns: &mut Vec<TokenStream>,
 ) {
     for attribute in field
@@ -298,8 +459,12 @@ fn parse_belongs_to(
         .filter(|attribute| attribute.path.is_ident("belongs_to"))
     {
         let meta: Meta = attribute.parse_meta().unwrap(); //.unwrap_or_abort();
+        let mut on_delete = quote! { None };
+        let mut on_update = quote! { None };
+        let mut deferrable = false;
+        let mut schema = quote! { None };
 
-        const VALID_FORMAT: &str = r#"Expected `#[belongs_to(foreign_key="foreign_key_name", table_name="your table name")]`"#;
+        const VALID_FORMAT: &str = r#"Expected `#[belongs_to(foreign_key="foreign_key_name", table_name="your table name", schema="its schema", on_delete="cascade", on_update="cascade", deferrable=true)]`"#;
         if let Meta::List(meta) = meta {
             for meta in meta.nested {
                 if let NestedMeta::Meta(meta) = meta {
@@ -313,6 +478,14 @@ fn parse_belongs_to(
                         ) {
                             ("foreign_key", Lit::Str(lit)) => *belongs_to_foreign_key = Some(lit),
                             ("table_name", Lit::Str(lit)) => *belongs_to_table_name = Some(lit),
+                            ("schema", Lit::Str(lit)) => schema = quote! { Some(#lit.to_string()) },
+                            ("on_delete", Lit::Str(lit)) => {
+                                on_delete = parse_referential_action(lit)
+                            }
+                            ("on_update", Lit::Str(lit)) => {
+                                on_update = parse_referential_action(lit)
+                            }
+                            ("deferrable", Lit::Bool(lit)) => deferrable = lit.value,
                             _ => abort_call_site!(VALID_FORMAT),
                         },
 
@@ -329,12 +502,19 @@ fn parse_belongs_to(
             let struct_n = syn::LitStr::new(&struct_name, field.span());
             let constraint_name = table_to_constraint_name(&table_n_value);
             let constraint_n = syn::LitStr::new(&constraint_name, field.span());
+            let options = quote! {
+                schema: #schema,
+                on_delete: #on_delete,
+                on_update: #on_update,
+                deferrable: #deferrable,
+            };
             let belongs_to = build_belongs_to(
                 belongs_to_foreign_key.clone(),
                 struct_n,
                 table_n,
                 key.clone(),
                 constraint_n,
+                options,
             );
             belongs_to_vec.push(belongs_to);
             // let column = build_column_not_null(key.clone(), ty.clone());
@@ -343,12 +523,39 @@ fn parse_belongs_to(
     }
 }
 
+/// `"cascade"`, `"restrict"` or `"no action"`. `"set null"` and `"set default"`
+/// are rejected, the belongs_to column is generated NOT NULL without a DEFAULT.
+fn parse_referential_action(lit: LitStr) -> TokenStream {
+    let action = match lit.value().to_lowercase().replace('_', " ").as_str() {
+        "cascade" => quote! { ReferentialAction::Cascade },
+        "set null" | "set default" => abort!(
+            lit,
+            "a belongs_to column is NOT NULL without a DEFAULT, so it cannot be set to NULL \
+             or to its default; use \"cascade\", \"restrict\" or \"no action\""
+        ),
+        "restrict" => quote! { ReferentialAction::Restrict },
+        "no action" => quote! { ReferentialAction::NoAction },
+        _ => abort!(
+            lit,
+            r#"Expected one of "cascade", "restrict" or "no action""#
+        ),
+    };
+    quote! { Some(#action) }
+}
+
 fn build_column_primary_key_auto_increment(key: TokenStream) -> TokenStream {
     let data_type = quote! { serial() };
     let options = quote! {primary_key_unique()};
     build_column(key, data_type, options)
 }
 
+/// `#[primary_key]` on several fields makes a composite key, see Migrator::create_table
+fn build_column_primary_key(key: TokenStream, data_type: TokenStream) -> TokenStream {
+    let d = quote! { DataType::from(stringify!(#data_type).to_string()) };
+    let options = quote! {primary_key_unique()};
+    build_column(key, d, options)
+}
+
 fn build_column(key: TokenStream, data_type: TokenStream, options: TokenStream) -> TokenStream {
     quote! {
         Column {
@@ -390,6 +597,7 @@ fn build_belongs_to(
     table_n: LitStr,
     column_name: TokenStream,
     constraint_name: LitStr,
+    options: TokenStream,
 ) -> TokenStream {
     quote! {
             BelongsTo {
@@ -398,6 +606,29 @@ fn build_belongs_to(
                 foreign_key: #foreign_key.to_string(),
                 struct_name: #struct_n.to_string(),
                 table_name: #table_n.to_string(),
+                #options
+            }
+
+    }
+}
+
+/// Index named `<table>_<columns>_idx` unless the attribute names it
+fn build_index(table_name: &str, column_names: &[String], index_spec: IndexSpec) -> TokenStream {
+    for column in &index_spec.columns {
+        if !column_names.contains(column) {
+            abort_call_site!("index column `{}` is not a column of the table", column);
+        }
+    }
+    let name = index_spec
+        .name
+        .unwrap_or_else(|| format!("{}_{}_idx", table_name, index_spec.columns.join("_")));
+    let columns = index_spec.columns;
+    let unique = index_spec.unique;
+    quote! {
+            Index {
+                name: #name.to_string(),
+                columns: vec![#(#columns.to_string()),*],
+                unique: #unique,
             }
 
     }
@@ -405,46 +636,56 @@ fn build_belongs_to(
 
 fn build_entity(
     table_name: String,
+    schema: Option<LitStr>,
     has_many: TokenStream,
     belongs_to: TokenStream,
     struct_name_str: LitStr,
     columns: TokenStream,
+    indexes: TokenStream,
 ) -> TokenStream {
+    let schema = match schema {
+        Some(schema) => quote! { Some(#schema.to_string()) },
+        None => quote! { None },
+    };
     quote! {
             let entity = Entity {
             table_name: #table_name.to_string(),
+            schema: #schema,
             struct_name: #struct_name_str.to_string(),
             belongs_to: #belongs_to,
             has_many: #has_many,
             columns: #columns,
+            indexes: #indexes,
             };
     }
 }
-fn build_preloads() -> TokenStream {
+fn build_preloads(has_many_entities: Vec<TokenStream>) -> TokenStream {
+    let has_many_entities = build_vec(has_many_entities);
     quote! {
-            let tuples : Vec<(String,QueryBuilder)>= entity.has_many.iter().map(|has_many| {
+            let has_many_entities: Vec<Entity> = #has_many_entities;
+            let tuples : Vec<(String,QueryBuilder)>= entity.has_many.iter().zip(has_many_entities.iter()).map(|(has_many, has_many_entity)| {
            (has_many.table_name.clone(),
-           select_table(has_many.table_name.clone().to_string())
+           has_many_entity.select()
            .where_(has_many.foreign_key.to_string()) )
         }).collect();
         let preloads: HashMap<_, _> = tuples.into_iter().collect();
     }
 }
 
-fn table_to_struct_name<'a>(table_n: &'a String) -> String {
+fn table_to_struct_name(table_n: &str) -> String {
     let first_letter = table_n[0..1].to_uppercase().to_string().to_owned();
     let struct_name: &str = &table_n[1..table_n.len() - 1];
     first_letter + struct_name
 }
 
-fn table_to_constraint_name<'a>(table_n: &'a String) -> String {
+fn table_to_constraint_name(table_n: &str) -> String {
     let struct_name: &str = &table_n[0..table_n.len() - 1];
     String::from("fk_") + struct_name
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{table_to_struct_name, table_to_constraint_name};
+    use crate::{table_to_constraint_name, table_to_struct_name};
 
     #[test]
     fn table_to_struct_name_test() {
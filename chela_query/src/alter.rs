@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use crate::{
+    create::{ColumnDef, DataType, TableConstraint},
+    display::display_comma_separated,
+    query::{Expr, Ident, ObjectName},
+};
+
+/// `ALTER TABLE <name> <operation>, ...`
+#[derive(Debug, PartialEq, Clone)]
+pub struct AlterTableStmt {
+    /// Table name
+    pub name: ObjectName,
+    /// Applied in order, RENAME COLUMN has to be the only one of its statement
+    pub operations: Vec<AlterTableOperation>,
+}
+
+impl Display for AlterTableStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ALTER TABLE {} {}",
+            self.name,
+            display_comma_separated(&self.operations)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum AlterTableOperation {
+    /// `ADD COLUMN <column_def>`
+    AddColumn { column_def: ColumnDef },
+    /// `DROP COLUMN [ IF EXISTS ] <column_name> [ CASCADE ]`
+    DropColumn {
+        column_name: Ident,
+        if_exists: bool,
+        cascade: bool,
+    },
+    /// `RENAME COLUMN <old_column_name> TO <new_column_name>`
+    RenameColumn {
+        old_column_name: Ident,
+        new_column_name: Ident,
+    },
+    /// `ALTER COLUMN <column_name> <op>`
+    AlterColumn {
+        column_name: Ident,
+        op: AlterColumnOperation,
+    },
+    /// `ADD <table_constraint>`
+    AddConstraint(TableConstraint),
+    /// `DROP CONSTRAINT [ IF EXISTS ] <name> [ CASCADE ]`
+    DropConstraint {
+        name: Ident,
+        if_exists: bool,
+        cascade: bool,
+    },
+}
+
+impl Display for AlterTableOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlterTableOperation::AddColumn { column_def } => {
+                write!(f, "ADD COLUMN {}", column_def)
+            }
+            AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                cascade,
+            } => write!(
+                f,
+                "DROP COLUMN {}{}{}",
+                if *if_exists { "IF EXISTS " } else { "" },
+                column_name,
+                if *cascade { " CASCADE" } else { "" }
+            ),
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => write!(
+                f,
+                "RENAME COLUMN {} TO {}",
+                old_column_name, new_column_name
+            ),
+            AlterTableOperation::AlterColumn { column_name, op } => {
+                write!(f, "ALTER COLUMN {} {}", column_name, op)
+            }
+            AlterTableOperation::AddConstraint(constraint) => write!(f, "ADD {}", constraint),
+            AlterTableOperation::DropConstraint {
+                name,
+                if_exists,
+                cascade,
+            } => write!(
+                f,
+                "DROP CONSTRAINT {}{}{}",
+                if *if_exists { "IF EXISTS " } else { "" },
+                name,
+                if *cascade { " CASCADE" } else { "" }
+            ),
+        }
+    }
+}
+
+/// What `ALTER COLUMN` changes about the column
+#[derive(Debug, PartialEq, Clone)]
+pub enum AlterColumnOperation {
+    /// `SET NOT NULL`
+    SetNotNull,
+    /// `DROP NOT NULL`
+    DropNotNull,
+    /// `SET DEFAULT <expr>`
+    SetDefault { value: Expr },
+    /// `DROP DEFAULT`
+    DropDefault,
+    /// `[SET DATA] TYPE <data_type> [USING <expr>]`
+    SetDataType {
+        data_type: DataType,
+        /// Converts the existing values when there is no implicit cast
+        using: Option<Expr>,
+    },
+}
+
+impl Display for AlterColumnOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlterColumnOperation::SetNotNull => write!(f, "SET NOT NULL"),
+            AlterColumnOperation::DropNotNull => write!(f, "DROP NOT NULL"),
+            AlterColumnOperation::SetDefault { value } => write!(f, "SET DEFAULT {}", value),
+            AlterColumnOperation::DropDefault => write!(f, "DROP DEFAULT"),
+            AlterColumnOperation::SetDataType { data_type, using } => {
+                write!(f, "SET DATA TYPE {}", data_type)?;
+                if let Some(using) = using {
+                    write!(f, " USING {}", using)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
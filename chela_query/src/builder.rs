@@ -1,21 +1,44 @@
 use crate::{
-    create::{ColumnDef, ColumnOption, ColumnOptionDef, CreateStmt, DataType, TableConstraint},
-    insert::InsertStmt,
+    alter::{AlterColumnOperation, AlterTableOperation, AlterTableStmt},
+    create::{
+        ColumnDef, ColumnOption, ColumnOptionDef, CreateStmt, DataType, ReferentialAction,
+        TableConstraint, TablePersistence,
+    },
+    delete::DeleteStmt,
+    drop::{DropBehavior, DropStmt, ObjectType},
+    index::{CreateIndexStmt, IndexMethod},
+    insert::{ConflictTarget, DoUpdate, InsertStmt, OnConflict, OnConflictAction, OnInsert},
+    operator::{
+        BinaryOperator, UnaryOperator, DOUBLE_COLON_PRECEDENCE, IS_PRECEDENCE, PATTERN_PRECEDENCE,
+    },
+    params::{BoundParam, CollectParams, Param},
     query::Expr,
     query::QueryStmt,
     query::SelectItem,
     query::SetExpr,
-    query::{Ident, ObjectName, Select, TableFactor, TableWithJoins},
+    query::{CastKind, Cte, SetOperator, With},
+    query::{
+        Fetch, Function, FunctionArg, Ident, Join, JoinConstraint, JoinOperator, ObjectName,
+        Offset, OffsetRows, OrderByExpr, Select, TableAlias, TableFactor, TableWithJoins,
+    },
+    query::{LockClause, LockType, NonBlock, Returning},
+    query::{
+        NamedWindowDefinition, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
+        WindowType,
+    },
+    schema::CreateSchemaStmt,
+    truncate::TruncateStmt,
+    update::{Assignment, UpdateStmt},
     values::{Value, Values},
 };
 
 #[derive(Debug, Clone)]
 pub struct CreateBuilder {
     // or_replace: bool,
-    // temporary: bool,
+    pub persistence: Option<TablePersistence>,
     // external: bool,
     // global: Option<bool>,
-    // if_not_exists: bool,
+    pub if_not_exists: bool,
     /// Table name
     pub name: ObjectName,
     /// Optional schema
@@ -29,24 +52,77 @@ pub fn create_table(name: String, columns: Vec<ColumnDef>) -> CreateBuilder {
     CreateBuilder::new(columns).name(name)
 }
 
+///Short hand for CreateBuilder::new().name_in(schema, name)
+pub fn create_table_in(schema: String, name: String, columns: Vec<ColumnDef>) -> CreateBuilder {
+    CreateBuilder::new(columns).name_in(schema, name)
+}
+
 pub fn select_table(name: String) -> QueryBuilder {
     QueryBuilder::new().select().from(name)
 }
 
+///Short hand for QueryBuilder::new().select().from_in(schema, name)
+pub fn select_table_in(schema: String, name: String) -> QueryBuilder {
+    QueryBuilder::new().select().from_in(schema, name)
+}
+
 impl CreateBuilder {
     pub fn new(columns: Vec<ColumnDef>) -> Self {
         CreateBuilder {
             // or_replace: false,
-            // temporary: false,
+            persistence: None,
             // external: false,
             // global: None,
-            // if_not_exists: false,
+            if_not_exists: false,
             name: ObjectName(vec![]),
-            columns: columns,
+            columns,
             constraints: vec![],
         }
     }
 
+    /// TEMPORARY, the table cannot be schema-qualified
+    pub fn temporary(mut self) -> CreateBuilder {
+        assert!(
+            self.name.0.len() <= 1,
+            "a TEMPORARY table cannot be created in a schema, got {}",
+            self.name
+        );
+        self.persistence = Some(TablePersistence::Temporary);
+        self
+    }
+
+    pub fn unlogged(mut self) -> CreateBuilder {
+        self.persistence = Some(TablePersistence::Unlogged);
+        self
+    }
+
+    pub fn if_not_exists(mut self) -> CreateBuilder {
+        self.if_not_exists = true;
+        self
+    }
+
+    pub fn constraint(mut self, constraint: TableConstraint) -> CreateBuilder {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Table level PRIMARY KEY, needed when the key spans several columns
+    pub fn primary_key_constraint(self, column_names: Vec<String>) -> CreateBuilder {
+        self.constraint(TableConstraint::Unique {
+            name: None,
+            columns: column_names.into_iter().map(Ident::new).collect(),
+            is_primary: true,
+        })
+    }
+
+    pub fn unique_constraint(self, column_names: Vec<String>) -> CreateBuilder {
+        self.constraint(TableConstraint::Unique {
+            name: None,
+            columns: column_names.into_iter().map(Ident::new).collect(),
+            is_primary: false,
+        })
+    }
+
     pub fn foreign_key_constraint(
         mut self,
         constraint_name: String,
@@ -54,24 +130,51 @@ impl CreateBuilder {
         foreign_table_name: String,
         referred_column_name: String,
     ) -> CreateBuilder {
-        self.constraints.push(TableConstraint::ForeignKey {
-            name: Some(Ident {
-                value: constraint_name,
-            }),
-            columns: vec![Ident { value: column_name }],
-            foreign_table: ObjectName(vec![Ident {
-                value: foreign_table_name,
-            }]),
-            referred_columns: vec![Ident {
-                value: referred_column_name,
-            }],
-            // on_delete: None,
-            // on_update: None,
-        });
+        self.constraints.push(
+            foreign_key(
+                constraint_name,
+                column_name,
+                foreign_table_name,
+                referred_column_name,
+            )
+            .build(),
+        );
         self
     }
+
+    /// Same as foreign_key_constraint, for a table in another schema
+    pub fn foreign_key_constraint_in(
+        self,
+        constraint_name: String,
+        column_name: String,
+        foreign_schema: String,
+        foreign_table_name: String,
+        referred_column_name: String,
+    ) -> CreateBuilder {
+        self.constraint(
+            foreign_key_in(
+                constraint_name,
+                column_name,
+                foreign_schema,
+                foreign_table_name,
+                referred_column_name,
+            )
+            .build(),
+        )
+    }
     pub fn name(mut self, name: String) -> CreateBuilder {
-        self.name = ObjectName(vec![Ident { value: name }]);
+        self.name = ObjectName(vec![Ident::new(name)]);
+        self
+    }
+
+    /// A TEMPORARY table lives in a session schema and cannot be given one
+    pub fn name_in(mut self, schema: String, name: String) -> CreateBuilder {
+        assert!(
+            self.persistence != Some(TablePersistence::Temporary),
+            "a TEMPORARY table cannot be created in schema {}",
+            schema
+        );
+        self.name = ObjectName::qualified(schema, name);
         self
     }
 
@@ -82,8 +185,8 @@ impl CreateBuilder {
         options_builder: Vec<ColumnOptionDef>,
     ) -> CreateBuilder {
         self.columns.push(ColumnDef {
-            name: Ident { value: name },
-            data_type: data_type,
+            name: Ident::new(name),
+            data_type,
             options: options_builder,
         });
         self
@@ -99,284 +202,2131 @@ impl CreateBuilder {
     }
 
     pub fn build(self) -> CreateStmt {
-        CreateStmt {
+        let create = CreateStmt {
+            persistence: self.persistence,
+            if_not_exists: self.if_not_exists,
             name: self.name,
             columns: self.columns,
             constraints: self.constraints,
-        }
+        };
+        assert_no_params(&create);
+        create
     }
 }
 
-pub struct DataTypeBuilder {
-    data_type: DataType,
+#[derive(Debug, Clone)]
+pub struct AlterBuilder {
+    /// Table name
+    pub name: ObjectName,
+    pub operations: Vec<AlterTableOperation>,
 }
 
-impl DataTypeBuilder {
+impl Default for AlterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Short hand for AlterBuilder::new().name(name)
+pub fn alter_table(name: String) -> AlterBuilder {
+    AlterBuilder::new().name(name)
+}
+
+///Short hand for AlterBuilder::new().name_in(schema, name)
+pub fn alter_table_in(schema: String, name: String) -> AlterBuilder {
+    AlterBuilder::new().name_in(schema, name)
+}
+
+impl AlterBuilder {
     pub fn new() -> Self {
-        DataTypeBuilder {
-            data_type: DataType::Custom(ObjectName(vec![])),
+        AlterBuilder {
+            name: ObjectName(vec![]),
+            operations: vec![],
         }
     }
 
-    pub fn varchar(mut self, length: Option<u64>) -> DataTypeBuilder {
-        self.data_type = DataType::Varchar(length);
+    pub fn name(mut self, name: String) -> AlterBuilder {
+        self.name = ObjectName(vec![Ident::new(name)]);
         self
     }
 
-    pub fn int(mut self, length: Option<u64>) -> DataTypeBuilder {
-        self.data_type = DataType::Int(length);
+    pub fn name_in(mut self, schema: String, name: String) -> AlterBuilder {
+        self.name = ObjectName::qualified(schema, name);
         self
     }
 
-    pub fn serial(mut self) -> DataTypeBuilder {
-        self.data_type = DataType::Custom(ObjectName(vec![Ident {
-            value: "SERIAL".to_string(),
-        }]));
+    pub fn add_column(
+        mut self,
+        name: String,
+        data_type: DataType,
+        options_builder: Vec<ColumnOptionDef>,
+    ) -> AlterBuilder {
+        self.operations.push(AlterTableOperation::AddColumn {
+            column_def: ColumnDef {
+                name: Ident::new(name),
+                data_type,
+                options: options_builder,
+            },
+        });
         self
     }
 
-    pub fn build(self) -> DataType {
-        self.data_type
+    pub fn drop_column(self, name: String) -> AlterBuilder {
+        self.drop_column_with(name, false, false)
     }
-}
-
-///Short hand for DataTypeBuilder::new().varchar(length)
-pub fn varchar(length: Option<u64>) -> DataType {
-    DataTypeBuilder::new().varchar(length).build()
-}
-pub fn int(length: Option<u64>) -> DataType {
-    DataTypeBuilder::new().int(length).build()
-}
-
-pub fn serial() -> DataType {
-    DataTypeBuilder::new().serial().build()
-}
-pub struct ColumnOptionDefBuilder {
-    options: Vec<ColumnOptionDef>,
-}
 
-impl ColumnOptionDefBuilder {
-    pub fn new() -> Self {
-        ColumnOptionDefBuilder { options: vec![] }
+    /// DROP COLUMN [IF EXISTS] name [CASCADE], CASCADE also drops what depends on the column
+    pub fn drop_column_with(
+        mut self,
+        name: String,
+        if_exists: bool,
+        cascade: bool,
+    ) -> AlterBuilder {
+        self.operations.push(AlterTableOperation::DropColumn {
+            column_name: Ident::new(name),
+            if_exists,
+            cascade,
+        });
+        self
     }
 
-    pub fn option(mut self, name: String, option: ColumnOption) -> ColumnOptionDefBuilder {
-        self.options.push(ColumnOptionDef {
-            name: Some(Ident { value: name }),
-            option: option,
+    /// Has to be the only operation of the statement
+    pub fn rename_column(mut self, old_name: String, new_name: String) -> AlterBuilder {
+        self.operations.push(AlterTableOperation::RenameColumn {
+            old_column_name: Ident::new(old_name),
+            new_column_name: Ident::new(new_name),
         });
-
         self
     }
 
-    pub fn primary_key_unique(mut self) -> ColumnOptionDefBuilder {
-        self.options.push(ColumnOptionDef {
-            name: None,
-            option: ColumnOption::Unique { is_primary: true },
+    pub fn alter_column(mut self, name: String, op: AlterColumnOperation) -> AlterBuilder {
+        self.operations.push(AlterTableOperation::AlterColumn {
+            column_name: Ident::new(name),
+            op,
         });
+        self
+    }
 
+    pub fn set_data_type(self, name: String, data_type: DataType) -> AlterBuilder {
+        self.alter_column(
+            name,
+            AlterColumnOperation::SetDataType {
+                data_type,
+                using: None,
+            },
+        )
+    }
+
+    pub fn set_default(self, name: String, value: Expr) -> AlterBuilder {
+        self.alter_column(name, AlterColumnOperation::SetDefault { value })
+    }
+
+    pub fn drop_default(self, name: String) -> AlterBuilder {
+        self.alter_column(name, AlterColumnOperation::DropDefault)
+    }
+
+    pub fn set_not_null(self, name: String) -> AlterBuilder {
+        self.alter_column(name, AlterColumnOperation::SetNotNull)
+    }
+
+    pub fn drop_not_null(self, name: String) -> AlterBuilder {
+        self.alter_column(name, AlterColumnOperation::DropNotNull)
+    }
+
+    pub fn add_constraint(mut self, constraint: TableConstraint) -> AlterBuilder {
+        self.operations
+            .push(AlterTableOperation::AddConstraint(constraint));
         self
     }
 
-    pub fn not_null(mut self) -> ColumnOptionDefBuilder {
-        self.options.push(ColumnOptionDef {
-            name: None,
-            option: ColumnOption::NotNull,
-        });
+    /// Same arguments as CreateBuilder::foreign_key_constraint
+    pub fn add_foreign_key_constraint(
+        self,
+        constraint_name: String,
+        column_name: String,
+        foreign_table_name: String,
+        referred_column_name: String,
+    ) -> AlterBuilder {
+        self.add_constraint(
+            foreign_key(
+                constraint_name,
+                column_name,
+                foreign_table_name,
+                referred_column_name,
+            )
+            .build(),
+        )
+    }
+
+    /// Same arguments as CreateBuilder::foreign_key_constraint_in
+    pub fn add_foreign_key_constraint_in(
+        self,
+        constraint_name: String,
+        column_name: String,
+        foreign_schema: String,
+        foreign_table_name: String,
+        referred_column_name: String,
+    ) -> AlterBuilder {
+        self.add_constraint(
+            foreign_key_in(
+                constraint_name,
+                column_name,
+                foreign_schema,
+                foreign_table_name,
+                referred_column_name,
+            )
+            .build(),
+        )
+    }
+
+    pub fn drop_constraint(self, name: String) -> AlterBuilder {
+        self.drop_constraint_with(name, false, false)
+    }
 
+    /// DROP CONSTRAINT [IF EXISTS] name [CASCADE], like drop_column_with
+    pub fn drop_constraint_with(
+        mut self,
+        name: String,
+        if_exists: bool,
+        cascade: bool,
+    ) -> AlterBuilder {
+        self.operations.push(AlterTableOperation::DropConstraint {
+            name: Ident::new(name),
+            if_exists,
+            cascade,
+        });
         self
     }
 
-    pub fn build(self) -> Vec<ColumnOptionDef> {
-        self.options
+    pub fn build(self) -> AlterTableStmt {
+        let alter = AlterTableStmt {
+            name: self.name,
+            operations: self.operations,
+        };
+        assert_no_params(&alter);
+        alter
     }
 }
 
-pub fn not_null() -> Vec<ColumnOptionDef> {
-    ColumnOptionDefBuilder::new().not_null().build()
+#[derive(Debug, Clone)]
+pub struct ForeignKeyBuilder {
+    pub name: Option<Ident>,
+    pub columns: Vec<Ident>,
+    pub foreign_table: ObjectName,
+    pub referred_columns: Vec<Ident>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+    pub deferrable: bool,
 }
-pub fn primary_key_unique() -> Vec<ColumnOptionDef> {
-    ColumnOptionDefBuilder::new().primary_key_unique().build()
+
+///Short hand for a single column ForeignKeyBuilder, same arguments as
+///CreateBuilder::foreign_key_constraint
+pub fn foreign_key(
+    constraint_name: String,
+    column_name: String,
+    foreign_table_name: String,
+    referred_column_name: String,
+) -> ForeignKeyBuilder {
+    ForeignKeyBuilder::new(
+        vec![column_name],
+        ObjectName(vec![Ident::new(foreign_table_name)]),
+        vec![referred_column_name],
+    )
+    .name(constraint_name)
 }
-#[derive(Debug, Clone)]
-pub struct QueryBuilder {
-    pub order_by: Option<String>,
-    pub r#where: Box<Expr>,
-    pub limit: Option<i64>,
-    // pub distinct: bool,
-    /// projection expressions
-    pub projection: Vec<SelectItem>,
-    /// FROM
-    pub from: Vec<TableWithJoins>, //Vec<TableWithJoins>,
-    /// WHERE
-    pub selection: Option<Expr>,
-    /// GROUP BY
-    pub group_by: Option<String>, //Vec<Expr>,
-    /// SORT BY (Hive)
-    pub sort_by: Vec<Expr>,
-    /// HAVING
-    pub having: Option<Expr>,
+
+///Short hand for foreign_key referring to `foreign_schema.foreign_table_name`
+pub fn foreign_key_in(
+    constraint_name: String,
+    column_name: String,
+    foreign_schema: String,
+    foreign_table_name: String,
+    referred_column_name: String,
+) -> ForeignKeyBuilder {
+    ForeignKeyBuilder::new(
+        vec![column_name],
+        ObjectName::qualified(foreign_schema, foreign_table_name),
+        vec![referred_column_name],
+    )
+    .name(constraint_name)
 }
 
-impl QueryBuilder {
-    pub fn new() -> Self {
-        Self {
-            order_by: None,
-            limit: None,
-            r#where: Box::new(Expr::Identifier(Ident {
-                value: "".to_string(),
-            })),
-            projection: Vec::new(),
-            from: Vec::new(),
-            selection: None,
-            group_by: None,
-            sort_by: Vec::new(),
-            having: None,
+impl ForeignKeyBuilder {
+    pub fn new(
+        column_names: Vec<String>,
+        foreign_table: ObjectName,
+        referred_column_names: Vec<String>,
+    ) -> Self {
+        ForeignKeyBuilder {
+            name: None,
+            columns: column_names.into_iter().map(Ident::new).collect(),
+            foreign_table,
+            referred_columns: referred_column_names.into_iter().map(Ident::new).collect(),
+            on_delete: None,
+            on_update: None,
+            deferrable: false,
         }
     }
-    pub fn select(mut self) -> QueryBuilder {
-        //projection: Vec<SelectItem>
-        self.projection = vec![SelectItem::Wildcard];
+
+    pub fn name(mut self, name: String) -> ForeignKeyBuilder {
+        self.name = Some(Ident::new(name));
         self
     }
 
-    pub fn from(mut self, from: String) -> QueryBuilder {
-        self.from = vec![TableWithJoins {
-            relation: TableFactor::Table {
-                name: ObjectName(vec![Ident { value: from }]),
-            },
-        }];
+    pub fn on_delete(mut self, action: ReferentialAction) -> ForeignKeyBuilder {
+        self.on_delete = Some(action);
         self
     }
 
-    pub fn in_list(mut self, list_of_ids: Vec<i32>) -> QueryBuilder {
-        let expr_value = list_of_ids
-            .into_iter()
-            .map(|value| Expr::Value(Value::Number(value.to_string(), false)))
-            .collect::<Vec<Expr>>();
-        self.selection = Some(Expr::InList {
-            expr: self.r#where.clone(),
-            list: expr_value,
+    pub fn on_update(mut self, action: ReferentialAction) -> ForeignKeyBuilder {
+        self.on_update = Some(action);
+        self
+    }
 
-            negated: false,
-        });
+    /// DEFERRABLE INITIALLY DEFERRED
+    pub fn deferrable(mut self) -> ForeignKeyBuilder {
+        self.deferrable = true;
         self
     }
 
-    pub fn where_(mut self, id: String) -> QueryBuilder {
-        self.r#where = Box::new(Expr::Identifier(Ident { value: id }));
+    pub fn build(self) -> TableConstraint {
+        TableConstraint::ForeignKey {
+            name: self.name,
+            columns: self.columns,
+            foreign_table: self.foreign_table,
+            referred_columns: self.referred_columns,
+            on_delete: self.on_delete,
+            on_update: self.on_update,
+            deferrable: self.deferrable,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DropBuilder {
+    pub object_type: ObjectType,
+    pub if_exists: bool,
+    pub names: Vec<ObjectName>,
+    pub behavior: Option<DropBehavior>,
+}
+
+///Short hand for DropBuilder::new(ObjectType::Table).name(name)
+pub fn drop_table(name: String) -> DropBuilder {
+    DropBuilder::new(ObjectType::Table).name(name)
+}
+
+///Short hand for DropBuilder::new(ObjectType::Table).name_in(schema, name)
+pub fn drop_table_in(schema: String, name: String) -> DropBuilder {
+    DropBuilder::new(ObjectType::Table).name_in(schema, name)
+}
+
+///Short hand for DropBuilder::new(ObjectType::Index).name(name)
+pub fn drop_index(name: String) -> DropBuilder {
+    DropBuilder::new(ObjectType::Index).name(name)
+}
+
+impl DropBuilder {
+    pub fn new(object_type: ObjectType) -> Self {
+        DropBuilder {
+            object_type,
+            if_exists: false,
+            names: vec![],
+            behavior: None,
+        }
+    }
+
+    /// Appends an object to drop, one statement can drop several of the same type
+    pub fn name(mut self, name: String) -> DropBuilder {
+        self.names.push(ObjectName(vec![Ident::new(name)]));
         self
     }
 
-    pub fn group_by(mut self, group_by: Option<String>) -> QueryBuilder {
-        self.group_by = group_by;
+    pub fn name_in(mut self, schema: String, name: String) -> DropBuilder {
+        self.names.push(ObjectName::qualified(schema, name));
         self
     }
 
-    pub fn order_by(mut self, order_by: Option<String>) -> QueryBuilder {
-        self.order_by = order_by;
+    pub fn if_exists(mut self) -> DropBuilder {
+        self.if_exists = true;
         self
     }
 
-    pub fn limit(mut self, limit: Option<i64>) -> QueryBuilder {
-        self.limit = limit;
+    pub fn cascade(mut self) -> DropBuilder {
+        self.behavior = Some(DropBehavior::Cascade);
         self
     }
 
-    pub fn sort_by(mut self) -> QueryBuilder {
-        self.sort_by = vec![];
+    pub fn restrict(mut self) -> DropBuilder {
+        self.behavior = Some(DropBehavior::Restrict);
         self
     }
 
-    pub fn build(self) -> QueryStmt {
-        QueryStmt {
-            body: SetExpr::Select(Box::new(Select {
-                projection: self.projection,
-                from: self.from,
-                selection: self.selection,
-                group_by: self.group_by,
-                sort_by: self.sort_by,
-                having: self.having,
-            })),
-            order_by: self.order_by,
-            limit: self.limit,
+    pub fn build(self) -> DropStmt {
+        DropStmt {
+            object_type: self.object_type,
+            if_exists: self.if_exists,
+            names: self.names,
+            behavior: self.behavior,
         }
     }
 }
 
-pub struct InsertBuilder {
-    /// Only for Sqlite
-    // or: Option<SqliteOnConflict>,
-    /// INTO - optional keyword
-    pub into: bool,
-    /// TABLE
-    pub table_name: String,
-    /// COLUMNS
-    pub columns: Vec<Ident>,
-    /// Overwrite (Hive)
-    // pub overwrite: bool,
-    // A SQL query that specifies what to insert
-    pub source: QueryStmt,
-    // partitioned insert (Hive)
-    // partitioned: Option<Vec<Expr>>,
-    // Columns defined after PARTITION
-    // after_columns: Vec<Ident>,
-    // whether the insert has the table keyword (Hive)
-    // table: bool,
-    // on: Option<OnInsert>,
+#[derive(Debug, Clone)]
+pub struct TruncateBuilder {
+    pub table_names: Vec<ObjectName>,
+    pub restart_identity: bool,
+    pub behavior: Option<DropBehavior>,
 }
 
-impl InsertBuilder {
+impl Default for TruncateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Short hand for TruncateBuilder::new().table(name)
+pub fn truncate(name: String) -> TruncateBuilder {
+    TruncateBuilder::new().table(name)
+}
+
+///Short hand for TruncateBuilder::new().table_in(schema, name)
+pub fn truncate_in(schema: String, name: String) -> TruncateBuilder {
+    TruncateBuilder::new().table_in(schema, name)
+}
+
+impl TruncateBuilder {
     pub fn new() -> Self {
-        Self {
-            into: true,
-            table_name: "".to_string(),
-            columns: Vec::new(),
-            source: QueryStmt {
-                body: SetExpr::Values(Values(vec![vec![]])),
-                order_by: None,
-                limit: None,
-            },
+        TruncateBuilder {
+            table_names: vec![],
+            restart_identity: false,
+            behavior: None,
         }
     }
 
-    pub fn columns(mut self, columns: Vec<String>) -> InsertBuilder {
-        columns
-            .into_iter()
-            .for_each(|column| self.columns.push(Ident { value: column }));
+    /// Appends a table to empty
+    pub fn table(mut self, name: String) -> TruncateBuilder {
+        self.table_names.push(ObjectName(vec![Ident::new(name)]));
         self
     }
-    pub fn column(mut self, column: String) -> InsertBuilder {
-        self.columns.push(Ident { value: column });
+
+    pub fn table_in(mut self, schema: String, name: String) -> TruncateBuilder {
+        self.table_names.push(ObjectName::qualified(schema, name));
         self
     }
 
-    pub fn into(mut self, table: String) -> InsertBuilder {
-        self.table_name = table;
+    pub fn restart_identity(mut self) -> TruncateBuilder {
+        self.restart_identity = true;
         self
     }
 
-    pub fn values(mut self, values: Vec<String>) -> InsertBuilder {
-        let expr_value = values
-            .into_iter()
-            .map(|value| Expr::Value(Value::SingleQuotedString(value)))
-            .collect::<Vec<Expr>>();
-        self.source.body = SetExpr::Values(Values(vec![expr_value]));
+    pub fn cascade(mut self) -> TruncateBuilder {
+        self.behavior = Some(DropBehavior::Cascade);
         self
     }
 
-    pub fn build(self) -> InsertStmt {
-        InsertStmt {
-            into: true,
+    pub fn restrict(mut self) -> TruncateBuilder {
+        self.behavior = Some(DropBehavior::Restrict);
+        self
+    }
+
+    pub fn build(self) -> TruncateStmt {
+        TruncateStmt {
+            table_names: self.table_names,
+            restart_identity: self.restart_identity,
+            behavior: self.behavior,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexBuilder {
+    pub name: Option<Ident>,
+    pub table_name: ObjectName,
+    pub using: Option<IndexMethod>,
+    pub columns: Vec<OrderByExpr>,
+    pub unique: bool,
+    pub concurrently: bool,
+    pub if_not_exists: bool,
+    pub include: Vec<Ident>,
+    pub predicate: Option<Expr>,
+}
+
+impl Default for IndexBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Short hand for IndexBuilder::new().name(name).on(table)
+pub fn create_index(name: String, table: String) -> IndexBuilder {
+    IndexBuilder::new().name(name).on(table)
+}
+
+///Short hand for IndexBuilder::new().name(name).on_in(schema, table), the index
+///is created in the table's schema
+pub fn create_index_in(name: String, schema: String, table: String) -> IndexBuilder {
+    IndexBuilder::new().name(name).on_in(schema, table)
+}
+
+impl IndexBuilder {
+    pub fn new() -> Self {
+        IndexBuilder {
+            name: None,
+            table_name: ObjectName(vec![]),
+            using: None,
+            columns: vec![],
+            unique: false,
+            concurrently: false,
+            if_not_exists: false,
+            include: vec![],
+            predicate: None,
+        }
+    }
+
+    pub fn name(mut self, name: String) -> IndexBuilder {
+        self.name = Some(Ident::new(name));
+        self
+    }
+
+    pub fn on(mut self, table: String) -> IndexBuilder {
+        self.table_name = ObjectName(vec![Ident::new(table)]);
+        self
+    }
+
+    pub fn on_in(mut self, schema: String, table: String) -> IndexBuilder {
+        self.table_name = ObjectName::qualified(schema, table);
+        self
+    }
+
+    pub fn unique(mut self) -> IndexBuilder {
+        self.unique = true;
+        self
+    }
+
+    pub fn concurrently(mut self) -> IndexBuilder {
+        self.concurrently = true;
+        self
+    }
+
+    pub fn if_not_exists(mut self) -> IndexBuilder {
+        self.if_not_exists = true;
+        self
+    }
+
+    pub fn using(mut self, method: IndexMethod) -> IndexBuilder {
+        self.using = Some(method);
+        self
+    }
+
+    pub fn column(self, column: String) -> IndexBuilder {
+        self.expression(col(&column))
+    }
+
+    pub fn columns(self, columns: Vec<String>) -> IndexBuilder {
+        columns
+            .into_iter()
+            .fold(self, |index, column| index.column(column))
+    }
+
+    /// Indexes the value of `expr`, e.g. `lower(email)`
+    pub fn expression(self, expr: Expr) -> IndexBuilder {
+        self.ordered(OrderByExpr {
+            expr,
+            asc: None,
+            nulls_first: None,
+        })
+    }
+
+    /// Column or expression with its sort order, e.g. `col("created_at").desc()`
+    pub fn ordered(mut self, column: OrderByExpr) -> IndexBuilder {
+        let expr = match column.expr {
+            expr @ (Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Function(_)
+            | Expr::Nested(_)) => expr,
+            expr => Expr::Nested(Box::new(expr)),
+        };
+        self.columns.push(OrderByExpr { expr, ..column });
+        self
+    }
+
+    pub fn include(mut self, columns: Vec<String>) -> IndexBuilder {
+        self.include.extend(columns.into_iter().map(Ident::new));
+        self
+    }
+
+    /// WHERE, only rows matching `predicate` are indexed
+    pub fn selection(mut self, predicate: Expr) -> IndexBuilder {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    pub fn build(self) -> CreateIndexStmt {
+        let index = CreateIndexStmt {
+            name: self.name,
             table_name: self.table_name,
+            using: self.using,
             columns: self.columns,
-            source: self.source,
+            unique: self.unique,
+            concurrently: self.concurrently,
+            if_not_exists: self.if_not_exists,
+            include: self.include,
+            predicate: self.predicate,
+        };
+        assert_no_params(&index);
+        index
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    pub if_not_exists: bool,
+    pub name: Ident,
+}
+
+///Short hand for SchemaBuilder::new(name)
+pub fn create_schema(name: String) -> SchemaBuilder {
+    SchemaBuilder::new(name)
+}
+
+impl SchemaBuilder {
+    pub fn new(name: String) -> Self {
+        SchemaBuilder {
+            if_not_exists: false,
+            name: Ident::new(name),
+        }
+    }
+
+    pub fn if_not_exists(mut self) -> SchemaBuilder {
+        self.if_not_exists = true;
+        self
+    }
+
+    pub fn build(self) -> CreateSchemaStmt {
+        CreateSchemaStmt {
+            if_not_exists: self.if_not_exists,
+            name: self.name,
         }
     }
 }
 
-pub fn insert_into(table: String) -> InsertBuilder {
-    InsertBuilder::new().into(table)
+pub struct DataTypeBuilder {
+    data_type: DataType,
+}
+
+impl Default for DataTypeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataTypeBuilder {
+    pub fn new() -> Self {
+        DataTypeBuilder {
+            data_type: DataType::Custom(ObjectName(vec![])),
+        }
+    }
+
+    pub fn varchar(mut self, length: Option<u64>) -> DataTypeBuilder {
+        self.data_type = DataType::Varchar(length);
+        self
+    }
+
+    pub fn int(mut self, length: Option<u64>) -> DataTypeBuilder {
+        self.data_type = DataType::Int(length);
+        self
+    }
+
+    pub fn serial(mut self) -> DataTypeBuilder {
+        self.data_type = DataType::Custom(ObjectName(vec![Ident::new("SERIAL".to_string())]));
+        self
+    }
+
+    pub fn build(self) -> DataType {
+        self.data_type
+    }
+}
+
+///Short hand for DataTypeBuilder::new().varchar(length)
+pub fn varchar(length: Option<u64>) -> DataType {
+    DataTypeBuilder::new().varchar(length).build()
+}
+pub fn int(length: Option<u64>) -> DataType {
+    DataTypeBuilder::new().int(length).build()
+}
+
+pub fn serial() -> DataType {
+    DataTypeBuilder::new().serial().build()
+}
+pub struct ColumnOptionDefBuilder {
+    options: Vec<ColumnOptionDef>,
+}
+
+impl Default for ColumnOptionDefBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColumnOptionDefBuilder {
+    pub fn new() -> Self {
+        ColumnOptionDefBuilder { options: vec![] }
+    }
+
+    pub fn option(mut self, name: String, option: ColumnOption) -> ColumnOptionDefBuilder {
+        self.options.push(ColumnOptionDef {
+            name: Some(Ident::new(name)),
+            option,
+        });
+
+        self
+    }
+
+    pub fn primary_key_unique(mut self) -> ColumnOptionDefBuilder {
+        self.options.push(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::Unique { is_primary: true },
+        });
+
+        self
+    }
+
+    pub fn not_null(mut self) -> ColumnOptionDefBuilder {
+        self.options.push(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::NotNull,
+        });
+
+        self
+    }
+
+    pub fn build(self) -> Vec<ColumnOptionDef> {
+        self.options
+    }
+}
+
+pub fn not_null() -> Vec<ColumnOptionDef> {
+    ColumnOptionDefBuilder::new().not_null().build()
+}
+pub fn primary_key_unique() -> Vec<ColumnOptionDef> {
+    ColumnOptionDefBuilder::new().primary_key_unique().build()
+}
+#[derive(Debug, Clone)]
+pub struct QueryBuilder {
+    /// WITH
+    pub with: Option<With>,
+    /// Set by the UNION/EXCEPT/INTERSECT combinators, replaces the SELECT built
+    /// from the fields below
+    pub body: Option<SetExpr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub r#where: Box<Expr>,
+    pub limit: Option<i64>,
+    pub offset: Option<Offset>,
+    pub fetch: Option<Fetch>,
+    pub locks: Vec<LockClause>,
+    // pub distinct: bool,
+    /// projection expressions
+    pub projection: Vec<SelectItem>,
+    /// FROM
+    pub from: Vec<TableWithJoins>, //Vec<TableWithJoins>,
+    /// JOINs, attached to the last FROM table when the query is built
+    pub joins: Vec<Join>,
+    /// WHERE
+    pub selection: Option<Expr>,
+    /// GROUP BY
+    pub group_by: Vec<Expr>,
+    /// SORT BY (Hive)
+    pub sort_by: Vec<Expr>,
+    /// HAVING
+    pub having: Option<Expr>,
+    /// WINDOW
+    pub named_window: Vec<NamedWindowDefinition>,
+}
+
+impl Default for QueryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryBuilder {
+    pub fn new() -> Self {
+        Self {
+            with: None,
+            body: None,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            fetch: None,
+            locks: Vec::new(),
+            r#where: Box::new(Expr::Identifier(Ident::new("".to_string()))),
+            projection: Vec::new(),
+            from: Vec::new(),
+            joins: Vec::new(),
+            selection: None,
+            group_by: Vec::new(),
+            sort_by: Vec::new(),
+            having: None,
+            named_window: Vec::new(),
+        }
+    }
+    pub fn select(mut self) -> QueryBuilder {
+        //projection: Vec<SelectItem>
+        self.projection = vec![SelectItem::Wildcard];
+        self
+    }
+
+    pub fn projection(mut self, projection: Vec<SelectItem>) -> QueryBuilder {
+        self.projection = projection;
+        self
+    }
+
+    /// Replaces the `*` projection with an explicit list of columns
+    pub fn columns<I, S>(mut self, columns: I) -> QueryBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.projection = select_items(columns);
+        self
+    }
+
+    pub fn from(mut self, from: String) -> QueryBuilder {
+        self.from = vec![TableWithJoins {
+            relation: table(&from),
+            joins: vec![],
+        }];
+        self
+    }
+
+    pub fn from_in(mut self, schema: String, from: String) -> QueryBuilder {
+        self.from = vec![TableWithJoins {
+            relation: TableFactor::Table {
+                name: ObjectName::qualified(schema, from),
+                alias: None,
+            },
+            joins: vec![],
+        }];
+        self
+    }
+
+    pub fn from_as(mut self, from: String, alias: String) -> QueryBuilder {
+        self.from = vec![TableWithJoins {
+            relation: table_as(&from, &alias),
+            joins: vec![],
+        }];
+        self
+    }
+
+    /// FROM (subquery) AS alias
+    pub fn from_subquery(mut self, subquery: QueryBuilder, alias: String) -> QueryBuilder {
+        self.from = vec![TableWithJoins {
+            relation: TableFactor::Derived {
+                subquery: Box::new(subquery.build()),
+                alias: Some(TableAlias {
+                    name: Ident::new(alias),
+                    columns: vec![],
+                }),
+            },
+            joins: vec![],
+        }];
+        self
+    }
+
+    /// Appends `name AS (query)` to the WITH clause, the query can then be
+    /// selected from by name
+    pub fn with(self, name: String, query: QueryBuilder) -> QueryBuilder {
+        self.with_cte(name, vec![], query)
+    }
+
+    /// Same as with, but the CTE may refer to itself, as in `WITH RECURSIVE`
+    pub fn with_recursive(
+        mut self,
+        name: String,
+        columns: Vec<String>,
+        query: QueryBuilder,
+    ) -> QueryBuilder {
+        self = self.with_cte(name, columns, query);
+        if let Some(with) = &mut self.with {
+            with.recursive = true;
+        }
+        self
+    }
+
+    fn with_cte(mut self, name: String, columns: Vec<String>, query: QueryBuilder) -> QueryBuilder {
+        let with = self.with.get_or_insert(With {
+            recursive: false,
+            ctes: vec![],
+        });
+        with.ctes.push(Cte {
+            alias: TableAlias {
+                name: Ident::new(name),
+                columns: columns.into_iter().map(Ident::new).collect(),
+            },
+            query: query.build(),
+        });
+        self
+    }
+
+    /// Appends a join to the last table of the FROM clause, which may be set
+    /// before or after the join but has to be set before building
+    pub fn join_with(mut self, relation: TableFactor, join_operator: JoinOperator) -> QueryBuilder {
+        self.joins.push(Join {
+            relation,
+            join_operator,
+        });
+        self
+    }
+
+    pub fn join(self, relation: TableFactor, on: Expr) -> QueryBuilder {
+        self.join_with(relation, JoinOperator::Inner(JoinConstraint::On(on)))
+    }
+
+    pub fn join_using(self, relation: TableFactor, columns: Vec<String>) -> QueryBuilder {
+        let columns = columns.into_iter().map(Ident::new).collect();
+        self.join_with(
+            relation,
+            JoinOperator::Inner(JoinConstraint::Using(columns)),
+        )
+    }
+
+    pub fn left_join(self, relation: TableFactor, on: Expr) -> QueryBuilder {
+        self.join_with(relation, JoinOperator::LeftOuter(JoinConstraint::On(on)))
+    }
+
+    pub fn right_join(self, relation: TableFactor, on: Expr) -> QueryBuilder {
+        self.join_with(relation, JoinOperator::RightOuter(JoinConstraint::On(on)))
+    }
+
+    pub fn full_join(self, relation: TableFactor, on: Expr) -> QueryBuilder {
+        self.join_with(relation, JoinOperator::FullOuter(JoinConstraint::On(on)))
+    }
+
+    pub fn cross_join(self, relation: TableFactor) -> QueryBuilder {
+        self.join_with(relation, JoinOperator::CrossJoin)
+    }
+
+    pub fn in_list(mut self, list_of_ids: Vec<i32>) -> QueryBuilder {
+        let expr_value = list_of_ids
+            .into_iter()
+            .map(|value| Expr::Value(Value::Number(value.to_string(), false)))
+            .collect::<Vec<Expr>>();
+        self.selection = Some(Expr::InList {
+            expr: self.r#where.clone(),
+            list: expr_value,
+
+            negated: false,
+        });
+        self
+    }
+
+    pub fn selection(mut self, selection: Expr) -> QueryBuilder {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Same as in_list, but every id is sent as a bind parameter
+    pub fn bind_in_list<T: Param + 'static>(mut self, list: Vec<T>) -> QueryBuilder {
+        let expr_value = list.into_iter().map(param).collect::<Vec<Expr>>();
+        self.selection = Some(Expr::InList {
+            expr: self.r#where.clone(),
+            list: expr_value,
+            negated: false,
+        });
+        self
+    }
+
+    pub fn where_(mut self, id: String) -> QueryBuilder {
+        self.r#where = Box::new(Expr::Identifier(Ident::new(id)));
+        self
+    }
+
+    pub fn group_by(mut self, group_by: Vec<Expr>) -> QueryBuilder {
+        self.group_by = group_by;
+        self
+    }
+
+    /// HAVING, filters the groups after aggregation
+    pub fn having(mut self, having: Expr) -> QueryBuilder {
+        self.having = Some(having);
+        self
+    }
+
+    /// Appends `name AS (spec)` to the WINDOW clause, for `over_window(name)`
+    pub fn window(mut self, name: String, spec: WindowSpec) -> QueryBuilder {
+        self.named_window
+            .push(NamedWindowDefinition(Ident::new(name), spec));
+        self
+    }
+
+    /// Appends an ascending-by-default ORDER BY on the given column
+    pub fn order_by(mut self, order_by: Option<String>) -> QueryBuilder {
+        if let Some(order_by) = order_by {
+            self.order_by.push(OrderByExpr {
+                expr: col(&order_by),
+                asc: None,
+                nulls_first: None,
+            });
+        }
+        self
+    }
+
+    /// Appends an ORDER BY expression, e.g. `col("name").desc().nulls_last()`
+    pub fn order_by_expr(mut self, order_by: OrderByExpr) -> QueryBuilder {
+        self.order_by.push(order_by);
+        self
+    }
+
+    /// LIMIT, replaces any FETCH FIRST set before
+    pub fn limit(mut self, limit: Option<i64>) -> QueryBuilder {
+        self.limit = limit;
+        self.fetch = None;
+        self
+    }
+
+    pub fn offset(mut self, offset: Option<i64>) -> QueryBuilder {
+        self.offset = offset.map(|offset| Offset {
+            value: Expr::from(offset),
+            rows: OffsetRows::None,
+        });
+        self
+    }
+
+    /// `FETCH FIRST <N> ROWS ONLY`, the standard spelling of LIMIT, replaces
+    /// any LIMIT set before
+    pub fn fetch(self, fetch: Option<i64>) -> QueryBuilder {
+        self.fetch_first(fetch, false)
+    }
+
+    /// `FETCH FIRST <N> ROWS WITH TIES`, requires an ORDER BY
+    pub fn fetch_with_ties(self, fetch: Option<i64>) -> QueryBuilder {
+        self.fetch_first(fetch, true)
+    }
+
+    fn fetch_first(mut self, fetch: Option<i64>, with_ties: bool) -> QueryBuilder {
+        self.fetch = fetch.map(|quantity| Fetch {
+            with_ties,
+            quantity: Some(Expr::from(quantity)),
+        });
+        self.limit = None;
+        self
+    }
+
+    pub fn sort_by(mut self) -> QueryBuilder {
+        self.sort_by = vec![];
+        self
+    }
+
+    pub fn build(mut self) -> QueryStmt {
+        if self.body.is_some() {
+            assert!(
+                self.projection.is_empty()
+                    && self.from.is_empty()
+                    && self.joins.is_empty()
+                    && self.selection.is_none()
+                    && self.group_by.is_empty()
+                    && self.sort_by.is_empty()
+                    && self.having.is_none()
+                    && self.named_window.is_empty(),
+                "SELECT clause set after a set operation, set it on an operand instead"
+            );
+        }
+        assert!(
+            self.joins.is_empty() || !self.from.is_empty(),
+            "JOIN without a FROM table, call from() as well"
+        );
+        if let Some(last) = self.from.last_mut() {
+            last.joins.append(&mut self.joins);
+        }
+        let query = QueryStmt {
+            with: self.with,
+            body: self.body.unwrap_or_else(|| {
+                SetExpr::Select(Box::new(Select {
+                    projection: self.projection,
+                    from: self.from,
+                    selection: self.selection,
+                    group_by: self.group_by,
+                    sort_by: self.sort_by,
+                    having: self.having,
+                    named_window: self.named_window,
+                }))
+            }),
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            fetch: self.fetch,
+            locks: self.locks,
+        };
+        query.number_params();
+        query
+    }
+
+    /// FOR UPDATE, rendered after LIMIT/OFFSET
+    pub fn for_update(self) -> LockBuilder {
+        self.lock(LockType::Update)
+    }
+
+    /// FOR SHARE, rendered after LIMIT/OFFSET
+    pub fn for_share(self) -> LockBuilder {
+        self.lock(LockType::Share)
+    }
+
+    /// Starts a FOR <lock_type> clause, OF/NOWAIT/SKIP LOCKED are set on the returned builder
+    pub fn lock(self, lock_type: LockType) -> LockBuilder {
+        LockBuilder::new(self, lock_type)
+    }
+
+    /// `self UNION other`, ORDER BY and LIMIT set afterwards apply to the whole.
+    /// SELECT clauses such as FROM or WHERE belong on the operands, building
+    /// panics if one is set after the set operation.
+    pub fn union(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Union, false, other)
+    }
+
+    pub fn union_all(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Union, true, other)
+    }
+
+    pub fn intersect(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Intersect, false, other)
+    }
+
+    pub fn intersect_all(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Intersect, true, other)
+    }
+
+    pub fn except(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Except, false, other)
+    }
+
+    pub fn except_all(self, other: QueryBuilder) -> QueryBuilder {
+        self.set_operation(SetOperator::Except, true, other)
+    }
+
+    fn set_operation(self, op: SetOperator, all: bool, other: QueryBuilder) -> QueryBuilder {
+        QueryBuilder {
+            body: Some(SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(set_operand(self.build())),
+                right: Box::new(set_operand(other.build())),
+            }),
+            ..QueryBuilder::new()
+        }
+    }
+}
+
+/// Postgres takes no bind parameters in DDL, a value bound there would be sent
+/// as a `$n` placeholder the server has nothing to fill in for
+fn assert_no_params(stmt: &impl CollectParams) {
+    let mut params = Vec::new();
+    stmt.collect_params(&mut params);
+    assert!(
+        params.is_empty(),
+        "bind parameters are not allowed in DDL, use a literal instead"
+    );
+}
+
+/// The body of `query`, parenthesized when its own WITH, ORDER BY, LIMIT or lock
+/// would otherwise apply to the whole set operation
+fn set_operand(query: QueryStmt) -> SetExpr {
+    if query.with.is_none()
+        && query.order_by.is_empty()
+        && query.limit.is_none()
+        && query.offset.is_none()
+        && query.fetch.is_none()
+        && query.locks.is_empty()
+    {
+        query.body
+    } else {
+        SetExpr::Query(Box::new(query))
+    }
+}
+
+pub struct LockBuilder {
+    query: QueryBuilder,
+    lock: LockClause,
+}
+
+impl LockBuilder {
+    pub fn new(query: QueryBuilder, lock_type: LockType) -> Self {
+        LockBuilder {
+            query,
+            lock: LockClause {
+                lock_type,
+                of: vec![],
+                nonblock: None,
+            },
+        }
+    }
+
+    /// OF table, restricts the lock to the rows of `table`, an unqualified
+    /// table name or alias
+    pub fn of(mut self, table: impl Into<String>) -> LockBuilder {
+        self.lock.of.push(Ident::new(table));
+        self
+    }
+
+    pub fn nowait(mut self) -> LockBuilder {
+        self.lock.nonblock = Some(NonBlock::Nowait);
+        self
+    }
+
+    pub fn skip_locked(mut self) -> LockBuilder {
+        self.lock.nonblock = Some(NonBlock::SkipLocked);
+        self
+    }
+
+    /// Back to the query, with the lock clause appended
+    pub fn end(self) -> QueryBuilder {
+        let mut query = self.query;
+        query.locks.push(self.lock);
+        query
+    }
+
+    ///Short hand for end().lock(lock_type)
+    pub fn lock(self, lock_type: LockType) -> LockBuilder {
+        self.end().lock(lock_type)
+    }
+
+    ///Short hand for end().for_update()
+    pub fn for_update(self) -> LockBuilder {
+        self.end().for_update()
+    }
+
+    ///Short hand for end().for_share()
+    pub fn for_share(self) -> LockBuilder {
+        self.end().for_share()
+    }
+
+    ///Short hand for end().build()
+    pub fn build(self) -> QueryStmt {
+        self.end().build()
+    }
+}
+
+pub struct InsertBuilder {
+    /// Only for Sqlite
+    // or: Option<SqliteOnConflict>,
+    /// INTO - optional keyword
+    pub into: bool,
+    /// TABLE
+    pub table_name: ObjectName,
+    /// COLUMNS
+    pub columns: Vec<Ident>,
+    /// Overwrite (Hive)
+    // pub overwrite: bool,
+    // A SQL query that specifies what to insert, `DEFAULT VALUES` when absent
+    pub source: Option<QueryStmt>,
+    // partitioned insert (Hive)
+    // partitioned: Option<Vec<Expr>>,
+    // Columns defined after PARTITION
+    // after_columns: Vec<Ident>,
+    // whether the insert has the table keyword (Hive)
+    // table: bool,
+    /// ON CONFLICT
+    pub on: Option<OnInsert>,
+    /// RETURNING
+    pub returning: Option<Returning>,
+}
+
+impl Default for InsertBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InsertBuilder {
+    pub fn new() -> Self {
+        Self {
+            into: true,
+            table_name: ObjectName(vec![]),
+            columns: Vec::new(),
+            source: None,
+            on: None,
+            returning: None,
+        }
+    }
+
+    pub fn columns(mut self, columns: Vec<String>) -> InsertBuilder {
+        columns
+            .into_iter()
+            .for_each(|column| self.columns.push(Ident::new(column)));
+        self
+    }
+    pub fn column(mut self, column: String) -> InsertBuilder {
+        self.columns.push(Ident::new(column));
+        self
+    }
+
+    pub fn into(mut self, table: String) -> InsertBuilder {
+        self.table_name = ObjectName(vec![Ident::new(table)]);
+        self
+    }
+
+    pub fn into_in(mut self, schema: String, table: String) -> InsertBuilder {
+        self.table_name = ObjectName::qualified(schema, table);
+        self
+    }
+
+    pub fn values(mut self, values: Vec<String>) -> InsertBuilder {
+        let expr_value = values
+            .into_iter()
+            .map(|value| Expr::Value(Value::SingleQuotedString(value)))
+            .collect::<Vec<Expr>>();
+        self.source = Some(values_source(vec![expr_value]));
+        self
+    }
+
+    /// Same as values, but every value is sent as a bind parameter
+    pub fn bind_values(mut self, values: Vec<Box<dyn Param>>) -> InsertBuilder {
+        let expr_value = values.into_iter().map(param_boxed).collect::<Vec<Expr>>();
+        self.source = Some(values_source(vec![expr_value]));
+        self
+    }
+
+    /// Appends a row to the VALUES list, so many rows go in one statement
+    pub fn row(mut self, row: Vec<Expr>) -> InsertBuilder {
+        match &mut self.source {
+            Some(QueryStmt {
+                body: SetExpr::Values(values),
+                ..
+            }) => values.0.push(row),
+            _ => self.source = Some(values_source(vec![row])),
+        }
+        self
+    }
+
+    /// Same as row, but every value is sent as a bind parameter
+    pub fn bind_row(self, row: Vec<Box<dyn Param>>) -> InsertBuilder {
+        let row = row.into_iter().map(param_boxed).collect::<Vec<Expr>>();
+        self.row(row)
+    }
+
+    /// INSERT ... SELECT, values bound in the query are numbered with the insert's
+    pub fn select(mut self, query: QueryBuilder) -> InsertBuilder {
+        self.source = Some(query.build());
+        self
+    }
+
+    /// INSERT ... DEFAULT VALUES, every column takes its default, so the
+    /// column list is cleared as well
+    pub fn default_values(mut self) -> InsertBuilder {
+        self.columns.clear();
+        self.source = None;
+        self
+    }
+
+    /// ON CONFLICT (columns), DO NOTHING unless an update is set on the returned builder
+    pub fn on_conflict(self, columns: Vec<String>) -> OnConflictBuilder {
+        let columns = columns.into_iter().map(Ident::new).collect();
+        OnConflictBuilder::new(self, ConflictTarget::Columns(columns))
+    }
+
+    /// ON CONFLICT ON CONSTRAINT name, DO NOTHING unless an update is set on the
+    /// returned builder
+    pub fn on_conflict_on_constraint(self, constraint_name: String) -> OnConflictBuilder {
+        let constraint_name = ObjectName(vec![Ident::new(constraint_name)]);
+        OnConflictBuilder::new(self, ConflictTarget::OnConstraint(constraint_name))
+    }
+
+    /// ON CONFLICT DO NOTHING, skips a row colliding on any unique constraint
+    pub fn do_nothing(mut self) -> InsertBuilder {
+        self.on = Some(OnInsert::OnConflict(OnConflict {
+            conflict_target: None,
+            action: OnConflictAction::DoNothing,
+        }));
+        self
+    }
+
+    /// RETURNING, so generated values such as a SERIAL id come back with the insert
+    pub fn returning(mut self, returning: Vec<SelectItem>) -> InsertBuilder {
+        self.returning = Some(Returning(returning));
+        self
+    }
+
+    pub fn build(self) -> InsertStmt {
+        let insert = InsertStmt {
+            into: true,
+            table_name: self.table_name,
+            columns: self.columns,
+            source: self.source,
+            on: self.on,
+            returning: self.returning,
+        };
+        insert.number_params();
+        insert
+    }
+}
+
+/// Collects the ON CONFLICT clause of an insert, DO UPDATE always has the
+/// conflict target Postgres requires for it
+pub struct OnConflictBuilder {
+    insert: InsertBuilder,
+    conflict_target: ConflictTarget,
+    do_update: DoUpdate,
+}
+
+impl OnConflictBuilder {
+    pub fn new(insert: InsertBuilder, conflict_target: ConflictTarget) -> Self {
+        OnConflictBuilder {
+            insert,
+            conflict_target,
+            do_update: DoUpdate {
+                assignments: vec![],
+                selection: None,
+            },
+        }
+    }
+
+    /// Adds `column = value` to DO UPDATE SET, use `excluded(column)` for the proposed value
+    pub fn do_update_set(mut self, column: String, value: Expr) -> OnConflictBuilder {
+        self.do_update.assignments.push(Assignment {
+            id: Ident::new(column),
+            value,
+        });
+        self
+    }
+
+    /// Overwrites every given column with the value proposed for insertion
+    pub fn do_update_set_excluded(self, columns: Vec<String>) -> OnConflictBuilder {
+        columns.into_iter().fold(self, |on_conflict, column| {
+            let value = excluded(&column);
+            on_conflict.do_update_set(column, value)
+        })
+    }
+
+    /// Only rows matching `selection` are updated, the others are left as they are
+    pub fn do_update_where(mut self, selection: Expr) -> OnConflictBuilder {
+        self.do_update.selection = Some(selection);
+        self
+    }
+
+    /// Back to the insert, DO UPDATE when a column is set, DO NOTHING otherwise
+    pub fn end(self) -> InsertBuilder {
+        let action = if self.do_update.assignments.is_empty() {
+            OnConflictAction::DoNothing
+        } else {
+            OnConflictAction::DoUpdate(self.do_update)
+        };
+        let mut insert = self.insert;
+        insert.on = Some(OnInsert::OnConflict(OnConflict {
+            conflict_target: Some(self.conflict_target),
+            action,
+        }));
+        insert
+    }
+
+    ///Short hand for end().returning(returning)
+    pub fn returning(self, returning: Vec<SelectItem>) -> InsertBuilder {
+        self.end().returning(returning)
+    }
+
+    ///Short hand for end().build()
+    pub fn build(self) -> InsertStmt {
+        self.end().build()
+    }
+}
+
+fn values_source(rows: Vec<Vec<Expr>>) -> QueryStmt {
+    QueryStmt {
+        with: None,
+        body: SetExpr::Values(Values(rows)),
+        order_by: vec![],
+        limit: None,
+        offset: None,
+        fetch: None,
+        locks: vec![],
+    }
+}
+
+pub fn insert_into(table: String) -> InsertBuilder {
+    InsertBuilder::new().into(table)
+}
+
+///Short hand for InsertBuilder::new().into_in(schema, table)
+pub fn insert_into_in(schema: String, table: String) -> InsertBuilder {
+    InsertBuilder::new().into_in(schema, table)
+}
+
+pub struct UpdateBuilder {
+    /// TABLE
+    pub table_name: ObjectName,
+    /// Column assignments
+    pub assignments: Vec<Assignment>,
+    /// WHERE
+    pub selection: Option<Expr>,
+    /// RETURNING
+    pub returning: Option<Returning>,
+}
+
+impl Default for UpdateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpdateBuilder {
+    pub fn new() -> Self {
+        Self {
+            table_name: ObjectName(vec![]),
+            assignments: Vec::new(),
+            selection: None,
+            returning: None,
+        }
+    }
+
+    pub fn table(mut self, table: String) -> UpdateBuilder {
+        self.table_name = ObjectName(vec![Ident::new(table)]);
+        self
+    }
+
+    pub fn table_in(mut self, schema: String, table: String) -> UpdateBuilder {
+        self.table_name = ObjectName::qualified(schema, table);
+        self
+    }
+
+    pub fn set(mut self, column: String, value: Expr) -> UpdateBuilder {
+        self.assignments.push(Assignment {
+            id: Ident::new(column),
+            value,
+        });
+        self
+    }
+
+    pub fn selection(mut self, selection: Expr) -> UpdateBuilder {
+        self.selection = Some(selection);
+        self
+    }
+
+    pub fn returning(mut self, returning: Vec<SelectItem>) -> UpdateBuilder {
+        self.returning = Some(Returning(returning));
+        self
+    }
+
+    pub fn build(self) -> UpdateStmt {
+        let update = UpdateStmt {
+            table_name: self.table_name,
+            assignments: self.assignments,
+            selection: self.selection,
+            returning: self.returning,
+        };
+        update.number_params();
+        update
+    }
+}
+
+///Short hand for UpdateBuilder::new().table(table)
+pub fn update(table: String) -> UpdateBuilder {
+    UpdateBuilder::new().table(table)
+}
+
+///Short hand for UpdateBuilder::new().table_in(schema, table)
+pub fn update_in(schema: String, table: String) -> UpdateBuilder {
+    UpdateBuilder::new().table_in(schema, table)
+}
+
+pub struct DeleteBuilder {
+    /// FROM
+    pub table_name: ObjectName,
+    /// WHERE
+    pub selection: Option<Expr>,
+    /// RETURNING
+    pub returning: Option<Returning>,
+}
+
+impl Default for DeleteBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeleteBuilder {
+    pub fn new() -> Self {
+        Self {
+            table_name: ObjectName(vec![]),
+            selection: None,
+            returning: None,
+        }
+    }
+
+    pub fn from(mut self, table: String) -> DeleteBuilder {
+        self.table_name = ObjectName(vec![Ident::new(table)]);
+        self
+    }
+
+    pub fn from_in(mut self, schema: String, table: String) -> DeleteBuilder {
+        self.table_name = ObjectName::qualified(schema, table);
+        self
+    }
+
+    pub fn selection(mut self, selection: Expr) -> DeleteBuilder {
+        self.selection = Some(selection);
+        self
+    }
+
+    pub fn returning(mut self, returning: Vec<SelectItem>) -> DeleteBuilder {
+        self.returning = Some(Returning(returning));
+        self
+    }
+
+    pub fn build(self) -> DeleteStmt {
+        let delete = DeleteStmt {
+            table_name: self.table_name,
+            selection: self.selection,
+            returning: self.returning,
+        };
+        delete.number_params();
+        delete
+    }
+}
+
+///Short hand for DeleteBuilder::new().from(table)
+pub fn delete_from(table: String) -> DeleteBuilder {
+    DeleteBuilder::new().from(table)
+}
+
+///Short hand for DeleteBuilder::new().from_in(schema, table)
+pub fn delete_from_in(schema: String, table: String) -> DeleteBuilder {
+    DeleteBuilder::new().from_in(schema, table)
+}
+
+///Short hand for a value sent as a bind parameter, numbered `$n` when the
+///statement holding it is built
+pub fn param<T: Param + 'static>(value: T) -> Expr {
+    param_boxed(Box::new(value))
+}
+
+///Short hand for param with an already boxed value
+pub fn param_boxed(value: Box<dyn Param>) -> Expr {
+    Expr::Value(Value::Param(BoundParam::new(value)))
+}
+
+///Short hand for Expr::Identifier, `table.column` gives an Expr::CompoundIdentifier
+pub fn col(name: &str) -> Expr {
+    if name.contains('.') {
+        Expr::CompoundIdentifier(
+            name.split('.')
+                .map(|part| Ident::new(part.to_string()))
+                .collect(),
+        )
+    } else {
+        Expr::Identifier(Ident::new(name.to_string()))
+    }
+}
+
+pub struct FunctionBuilder {
+    pub name: ObjectName,
+    pub args: Vec<FunctionArg>,
+    pub distinct: bool,
+    pub filter: Option<Expr>,
+    pub over: Option<WindowType>,
+}
+
+impl FunctionBuilder {
+    pub fn new(name: &str, args: Vec<FunctionArg>) -> Self {
+        FunctionBuilder {
+            name: ObjectName(vec![Ident::new(name.to_string())]),
+            args,
+            distinct: false,
+            filter: None,
+            over: None,
+        }
+    }
+
+    /// DISTINCT inside an aggregate call, e.g. `count(DISTINCT user_id)`
+    pub fn distinct(mut self) -> FunctionBuilder {
+        self.distinct = true;
+        self
+    }
+
+    /// FILTER (WHERE ...) on an aggregate call, only matching rows are aggregated
+    pub fn filter(mut self, filter: Expr) -> FunctionBuilder {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// OVER (spec), turns the call into a window function
+    pub fn over(mut self, spec: WindowSpec) -> FunctionBuilder {
+        self.over = Some(WindowType::WindowSpec(spec));
+        self
+    }
+
+    /// OVER name, for a window defined with `QueryBuilder::window`
+    pub fn over_window(mut self, name: &str) -> FunctionBuilder {
+        self.over = Some(WindowType::NamedWindow(Ident::new(name.to_string())));
+        self
+    }
+
+    pub fn build(self) -> Expr {
+        Expr::Function(Function {
+            name: self.name,
+            args: self.args,
+            distinct: self.distinct,
+            filter: self.filter.map(Box::new),
+            over: self.over,
+        })
+    }
+}
+
+impl From<FunctionBuilder> for Expr {
+    fn from(function: FunctionBuilder) -> Self {
+        function.build()
+    }
+}
+
+///Short hand for FunctionBuilder::new(name, args), a call of the function `name`
+pub fn function(name: &str, args: Vec<Expr>) -> FunctionBuilder {
+    FunctionBuilder::new(name, args.into_iter().map(FunctionArg::Unnamed).collect())
+}
+
+///Short hand for count(expr)
+pub fn count(expr: Expr) -> FunctionBuilder {
+    function("count", vec![expr])
+}
+
+///Short hand for count(*)
+pub fn count_all() -> FunctionBuilder {
+    FunctionBuilder::new("count", vec![FunctionArg::Wildcard])
+}
+
+///Short hand for sum(expr)
+pub fn sum(expr: Expr) -> FunctionBuilder {
+    function("sum", vec![expr])
+}
+
+///Short hand for avg(expr)
+pub fn avg(expr: Expr) -> FunctionBuilder {
+    function("avg", vec![expr])
+}
+
+///Short hand for min(expr), named so it does not shadow std::cmp::min
+pub fn min_of(expr: Expr) -> FunctionBuilder {
+    function("min", vec![expr])
+}
+
+///Short hand for max(expr), named so it does not shadow std::cmp::max
+pub fn max_of(expr: Expr) -> FunctionBuilder {
+    function("max", vec![expr])
+}
+
+///Short hand for the NULL literal
+pub fn null() -> Expr {
+    Expr::Value(Value::Null)
+}
+
+///Short hand for a typed literal such as DATE '2020-01-01'
+pub fn typed_string(data_type: DataType, value: &str) -> Expr {
+    Expr::TypedString {
+        data_type,
+        value: value.to_string(),
+    }
+}
+
+/// Collects the WHEN branches of a CASE expression
+#[derive(Debug, Clone)]
+pub struct CaseBuilder {
+    pub operand: Option<Expr>,
+    pub conditions: Vec<Expr>,
+    pub results: Vec<Expr>,
+    pub else_result: Option<Expr>,
+}
+
+impl CaseBuilder {
+    /// A CASE needs at least one WHEN, so the first arm is given up front
+    pub fn new(operand: Option<Expr>, condition: Expr, result: Expr) -> Self {
+        Self {
+            operand,
+            conditions: vec![condition],
+            results: vec![result],
+            else_result: None,
+        }
+    }
+
+    /// WHEN condition THEN result, compared against the operand in a simple CASE
+    pub fn when(mut self, condition: impl Into<Expr>, result: impl Into<Expr>) -> CaseBuilder {
+        self.conditions.push(condition.into());
+        self.results.push(result.into());
+        self
+    }
+
+    pub fn else_(mut self, result: impl Into<Expr>) -> CaseBuilder {
+        self.else_result = Some(result.into());
+        self
+    }
+
+    pub fn end(self) -> Expr {
+        Expr::Case {
+            operand: self.operand.map(Box::new),
+            conditions: self.conditions,
+            results: self.results,
+            else_result: self.else_result.map(Box::new),
+        }
+    }
+}
+
+///Short hand for CaseBuilder::new(None, condition, result), a CASE of boolean conditions
+pub fn case(condition: impl Into<Expr>, result: impl Into<Expr>) -> CaseBuilder {
+    CaseBuilder::new(None, condition.into(), result.into())
+}
+
+///Short hand for CaseBuilder::new(Some(operand), value, result), as in CASE operand WHEN value ...
+pub fn case_of(operand: Expr, value: impl Into<Expr>, result: impl Into<Expr>) -> CaseBuilder {
+    CaseBuilder::new(Some(operand), value.into(), result.into())
+}
+
+///Short hand for row_number(), use with over()
+pub fn row_number() -> FunctionBuilder {
+    function("row_number", vec![])
+}
+
+///Short hand for rank(), use with over()
+pub fn rank() -> FunctionBuilder {
+    function("rank", vec![])
+}
+
+///Short hand for dense_rank(), use with over()
+pub fn dense_rank() -> FunctionBuilder {
+    function("dense_rank", vec![])
+}
+
+///Short hand for an empty WindowSpec, as in OVER ()
+pub fn window() -> WindowSpec {
+    WindowSpec::default()
+}
+
+///Short hand for UNBOUNDED PRECEDING
+pub fn unbounded_preceding() -> WindowFrameBound {
+    WindowFrameBound::Preceding(None)
+}
+
+///Short hand for <n> PRECEDING
+pub fn preceding(n: impl Into<Expr>) -> WindowFrameBound {
+    WindowFrameBound::Preceding(Some(Box::new(n.into())))
+}
+
+///Short hand for CURRENT ROW
+pub fn current_row() -> WindowFrameBound {
+    WindowFrameBound::CurrentRow
+}
+
+///Short hand for <n> FOLLOWING
+pub fn following(n: impl Into<Expr>) -> WindowFrameBound {
+    WindowFrameBound::Following(Some(Box::new(n.into())))
+}
+
+///Short hand for UNBOUNDED FOLLOWING
+pub fn unbounded_following() -> WindowFrameBound {
+    WindowFrameBound::Following(None)
+}
+
+///Short hand for a projection of plain columns, as used by SELECT and RETURNING
+pub fn select_items<I, S>(columns: I) -> Vec<SelectItem>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    columns
+        .into_iter()
+        .map(|column| SelectItem::UnnamedExpr(col(column.as_ref())))
+        .collect()
+}
+
+///Short hand for col("excluded.<name>"), the row proposed for insertion in ON CONFLICT DO UPDATE
+pub fn excluded(name: &str) -> Expr {
+    Expr::CompoundIdentifier(vec![
+        Ident::new("excluded".to_string()),
+        Ident::new(name.to_string()),
+    ])
+}
+
+///Short hand for TableFactor::Table without alias
+pub fn table(name: &str) -> TableFactor {
+    TableFactor::Table {
+        name: ObjectName(vec![Ident::new(name.to_string())]),
+        alias: None,
+    }
+}
+
+///Short hand for TableFactor::Table with `AS alias`
+pub fn table_as(name: &str, alias: &str) -> TableFactor {
+    TableFactor::Table {
+        name: ObjectName(vec![Ident::new(name.to_string())]),
+        alias: Some(TableAlias {
+            name: Ident::new(alias.to_string()),
+            columns: vec![],
+        }),
+    }
+}
+
+///Short hand for Expr::Subquery, a query used as a single value
+pub fn subquery(query: QueryBuilder) -> Expr {
+    Expr::Subquery(Box::new(query.build()))
+}
+
+///Short hand for EXISTS (query)
+pub fn exists(query: QueryBuilder) -> Expr {
+    Expr::Exists {
+        subquery: Box::new(query.build()),
+        negated: false,
+    }
+}
+
+///Short hand for NOT EXISTS (query)
+pub fn not_exists(query: QueryBuilder) -> Expr {
+    Expr::Exists {
+        subquery: Box::new(query.build()),
+        negated: true,
+    }
+}
+
+pub fn not(expr: Expr) -> Expr {
+    Expr::UnaryOp {
+        op: UnaryOperator::Not,
+        expr: Box::new(expr.nested_below(UnaryOperator::Not.precedence())),
+    }
+}
+
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        not(self)
+    }
+}
+
+impl Expr {
+    /// Wraps the expression in parentheses when it binds looser than `precedence`
+    fn nested_below(self, precedence: u8) -> Expr {
+        if self.precedence() < precedence {
+            Expr::Nested(Box::new(self))
+        } else {
+            self
+        }
+    }
+
+    fn binary_op(self, op: BinaryOperator, right: impl Into<Expr>) -> Expr {
+        let precedence = op.precedence();
+        // AND and OR are associative, every other operator needs parentheses
+        // around a right operand of the same precedence
+        let right_precedence = match op {
+            BinaryOperator::And | BinaryOperator::Or => precedence,
+            _ => precedence + 1,
+        };
+        Expr::BinaryOp {
+            left: Box::new(self.nested_below(precedence)),
+            op,
+            right: Box::new(right.into().nested_below(right_precedence)),
+        }
+    }
+
+    pub fn eq(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::Eq, right)
+    }
+
+    pub fn not_eq(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::NotEq, right)
+    }
+
+    pub fn lt(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::Lt, right)
+    }
+
+    pub fn lt_eq(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::LtEq, right)
+    }
+
+    pub fn gt(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::Gt, right)
+    }
+
+    pub fn gt_eq(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::GtEq, right)
+    }
+
+    pub fn and(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::And, right)
+    }
+
+    pub fn or(self, right: impl Into<Expr>) -> Expr {
+        self.binary_op(BinaryOperator::Or, right)
+    }
+
+    pub fn is_null(self) -> Expr {
+        Expr::IsNull(Box::new(self.nested_below(IS_PRECEDENCE + 1)))
+    }
+
+    pub fn is_not_null(self) -> Expr {
+        Expr::IsNotNull(Box::new(self.nested_below(IS_PRECEDENCE + 1)))
+    }
+
+    fn between_impl(self, low: impl Into<Expr>, high: impl Into<Expr>, negated: bool) -> Expr {
+        Expr::Between {
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            negated,
+            low: Box::new(low.into().nested_below(PATTERN_PRECEDENCE + 1)),
+            high: Box::new(high.into().nested_below(PATTERN_PRECEDENCE + 1)),
+        }
+    }
+
+    pub fn between(self, low: impl Into<Expr>, high: impl Into<Expr>) -> Expr {
+        self.between_impl(low, high, false)
+    }
+
+    pub fn not_between(self, low: impl Into<Expr>, high: impl Into<Expr>) -> Expr {
+        self.between_impl(low, high, true)
+    }
+
+    pub fn like(self, pattern: impl Into<Expr>) -> Expr {
+        Expr::Like {
+            negated: false,
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            pattern: Box::new(pattern.into().nested_below(PATTERN_PRECEDENCE + 1)),
+        }
+    }
+
+    pub fn not_like(self, pattern: impl Into<Expr>) -> Expr {
+        Expr::Like {
+            negated: true,
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            pattern: Box::new(pattern.into().nested_below(PATTERN_PRECEDENCE + 1)),
+        }
+    }
+
+    pub fn ilike(self, pattern: impl Into<Expr>) -> Expr {
+        Expr::ILike {
+            negated: false,
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            pattern: Box::new(pattern.into().nested_below(PATTERN_PRECEDENCE + 1)),
+        }
+    }
+
+    pub fn not_ilike(self, pattern: impl Into<Expr>) -> Expr {
+        Expr::ILike {
+            negated: true,
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            pattern: Box::new(pattern.into().nested_below(PATTERN_PRECEDENCE + 1)),
+        }
+    }
+
+    pub fn in_list<T: Into<Expr>>(self, list: Vec<T>) -> Expr {
+        Expr::InList {
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            list: list.into_iter().map(Into::into).collect(),
+            negated: false,
+        }
+    }
+
+    pub fn not_in_list<T: Into<Expr>>(self, list: Vec<T>) -> Expr {
+        Expr::InList {
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            list: list.into_iter().map(Into::into).collect(),
+            negated: true,
+        }
+    }
+
+    pub fn in_subquery(self, query: QueryBuilder) -> Expr {
+        Expr::InSubquery {
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            subquery: Box::new(query.build()),
+            negated: false,
+        }
+    }
+
+    pub fn not_in_subquery(self, query: QueryBuilder) -> Expr {
+        Expr::InSubquery {
+            expr: Box::new(self.nested_below(PATTERN_PRECEDENCE + 1)),
+            subquery: Box::new(query.build()),
+            negated: true,
+        }
+    }
+
+    /// CAST(self AS data_type)
+    pub fn cast(self, data_type: DataType) -> Expr {
+        Expr::Cast {
+            kind: CastKind::Cast,
+            expr: Box::new(self),
+            data_type,
+        }
+    }
+
+    /// self::data_type, the Postgres shorthand for cast
+    pub fn double_colon(self, data_type: DataType) -> Expr {
+        Expr::Cast {
+            kind: CastKind::DoubleColon,
+            expr: Box::new(self.nested_below(DOUBLE_COLON_PRECEDENCE)),
+            data_type,
+        }
+    }
+
+    /// Explicitly wraps the expression in parentheses
+    pub fn nested(self) -> Expr {
+        Expr::Nested(Box::new(self))
+    }
+
+    pub fn asc(self) -> OrderByExpr {
+        OrderByExpr {
+            expr: self,
+            asc: Some(true),
+            nulls_first: None,
+        }
+    }
+
+    pub fn desc(self) -> OrderByExpr {
+        OrderByExpr {
+            expr: self,
+            asc: Some(false),
+            nulls_first: None,
+        }
+    }
+}
+
+impl WindowSpec {
+    pub fn partition_by(mut self, partition_by: Vec<Expr>) -> WindowSpec {
+        self.partition_by = partition_by;
+        self
+    }
+
+    pub fn order_by(mut self, order_by: Vec<OrderByExpr>) -> WindowSpec {
+        self.order_by = order_by;
+        self
+    }
+
+    /// ROWS BETWEEN start AND end, counted in rows from the current one
+    pub fn rows_between(self, start: WindowFrameBound, end: WindowFrameBound) -> WindowSpec {
+        self.frame(WindowFrameUnits::Rows, start, Some(end))
+    }
+
+    /// RANGE BETWEEN start AND end, counted in ORDER BY values from the current row's
+    pub fn range_between(self, start: WindowFrameBound, end: WindowFrameBound) -> WindowSpec {
+        self.frame(WindowFrameUnits::Range, start, Some(end))
+    }
+
+    pub fn frame(
+        mut self,
+        units: WindowFrameUnits,
+        start_bound: WindowFrameBound,
+        end_bound: Option<WindowFrameBound>,
+    ) -> WindowSpec {
+        self.window_frame = Some(WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        });
+        self
+    }
+}
+
+impl OrderByExpr {
+    pub fn nulls_first(mut self) -> OrderByExpr {
+        self.nulls_first = Some(true);
+        self
+    }
+
+    pub fn nulls_last(mut self) -> OrderByExpr {
+        self.nulls_first = Some(false);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -400,7 +2350,7 @@ mod tests {
         let option_1_is_unique = option_1.into_iter().next().unwrap();
         let option_2_is_unique = option_2.into_iter().next().unwrap();
 
-        assert_eq!(option_1_is_unique, true);
-        assert_eq!(option_2_is_unique, false);
+        assert!(option_1_is_unique);
+        assert!(!option_2_is_unique);
     }
 }
@@ -9,10 +9,11 @@ use crate::{
 #[derive(Debug, PartialEq, Clone)]
 pub struct CreateStmt {
     // or_replace: bool,
-    // temporary: bool,
+    /// TEMPORARY or UNLOGGED, a regular table when None
+    pub persistence: Option<TablePersistence>,
     // external: bool,
     // global: Option<bool>,
-    // if_not_exists: bool,
+    pub if_not_exists: bool,
     /// Table name
     pub name: ObjectName,
     /// Optional schema
@@ -35,13 +36,13 @@ pub struct CreateStmt {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TableConstraint {
-    // `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<columns>)`
-    // Unique {
-    //     name: Option<Ident>,
-    //     columns: Vec<Ident>,
-    //     /// Whether this is a `PRIMARY KEY` or just a `UNIQUE` constraint
-    //     is_primary: bool,
-    // },
+    /// `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<columns>)`
+    Unique {
+        name: Option<Ident>,
+        columns: Vec<Ident>,
+        /// Whether this is a `PRIMARY KEY` or just a `UNIQUE` constraint
+        is_primary: bool,
+    },
     /// A referential integrity constraint (`[ CONSTRAINT <name> ] FOREIGN KEY (<columns>)
     /// REFERENCES <foreign_table> (<referred_columns>)
     /// { [ON DELETE <referential_action>] [ON UPDATE <referential_action>] |
@@ -52,42 +53,100 @@ pub enum TableConstraint {
         columns: Vec<Ident>,
         foreign_table: ObjectName,
         referred_columns: Vec<Ident>,
-        // on_delete: Option<ReferentialAction>,
-        // on_update: Option<ReferentialAction>,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+        /// `DEFERRABLE INITIALLY DEFERRED`, checked at commit instead of per statement
+        deferrable: bool,
     },
 }
 
 impl Display for TableConstraint {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            TableConstraint::Unique {
+                name,
+                columns,
+                is_primary,
+            } => {
+                if let Some(name) = name {
+                    write!(f, "CONSTRAINT {} ", name)?;
+                }
+                write!(
+                    f,
+                    "{} ({})",
+                    if *is_primary { "PRIMARY KEY" } else { "UNIQUE" },
+                    display_comma_separated(columns),
+                )?
+            }
             TableConstraint::ForeignKey {
                 name,
                 columns,
                 foreign_table,
                 referred_columns,
-            } => write!(
-                f,
-                ", CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
-                name.clone().unwrap().to_string(),
-                display_comma_separated(&columns),
-                foreign_table,
-                display_comma_separated(&referred_columns),
-            )?,
+                on_delete,
+                on_update,
+                deferrable,
+            } => {
+                if let Some(name) = name {
+                    write!(f, "CONSTRAINT {} ", name)?;
+                }
+                write!(
+                    f,
+                    "FOREIGN KEY ({}) REFERENCES {} ({})",
+                    display_comma_separated(columns),
+                    foreign_table,
+                    display_comma_separated(referred_columns),
+                )?;
+                if let Some(action) = on_delete {
+                    write!(f, " ON DELETE {}", action)?;
+                }
+                if let Some(action) = on_update {
+                    write!(f, " ON UPDATE {}", action)?;
+                }
+                if *deferrable {
+                    write!(f, " DEFERRABLE INITIALLY DEFERRED")?;
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// How a table is stored, Postgres takes at most one of these
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TablePersistence {
+    /// Dropped at the end of the session, lives in its own session schema
+    Temporary,
+    /// Not written to the write-ahead log
+    Unlogged,
+}
+
 impl Display for CreateStmt {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "CREATE TABLE {} (", self.name)?;
+        write!(
+            f,
+            "CREATE {persistence}TABLE {if_not_exists}{name} (",
+            persistence = match self.persistence {
+                Some(TablePersistence::Temporary) => "TEMPORARY ",
+                Some(TablePersistence::Unlogged) => "UNLOGGED ",
+                None => "",
+            },
+            if_not_exists = if self.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            name = self.name,
+        )?;
         for (i, column) in self.columns.iter().enumerate() {
             if i > 0 {
                 write!(f, ", ")?;
             }
             write!(f, "{}", column)?;
         }
-        write!(f, "{}", display_comma_separated(&self.constraints))?;
+        for constraint in &self.constraints {
+            write!(f, ", {}", constraint)?;
+        }
         write!(f, ")")?;
 
         Ok(())
@@ -110,19 +169,17 @@ impl ColumnDef {
             .into_iter()
             .filter_map(|option_def| option_def.option.is_primary())
             .collect();
-        let is_unique = option.into_iter().next().unwrap();
-        is_unique
+
+        option.into_iter().next().unwrap()
     }
 }
 impl Display for ColumnDef {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{} {} {}",
-            self.name,
-            self.data_type,
-            display_comma_separated(&self.options)
-        )
+        write!(f, "{} {}", self.name, self.data_type)?;
+        for option in &self.options {
+            write!(f, " {}", option)?;
+        }
+        Ok(())
     }
 }
 
@@ -165,8 +222,8 @@ pub enum ColumnOption {
     ForeignKey {
         foreign_table: ObjectName,
         referred_columns: Vec<Ident>,
-        // on_delete: Option<ReferentialAction>,
-        // on_update: Option<ReferentialAction>,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
     },
     /// `CHECK (<expr>)`
     Check(Expr),
@@ -194,19 +251,53 @@ impl Display for ColumnOption {
             ColumnOption::ForeignKey {
                 foreign_table,
                 referred_columns,
-                // on_delete,
-                // on_update,
-            } => write!(
-                f,
-                "FOREIGN KEY REFERENCES {} ({})",
-                foreign_table,
-                display_comma_separated(&referred_columns)
-            ),
+                on_delete,
+                on_update,
+            } => {
+                write!(
+                    f,
+                    "REFERENCES {} ({})",
+                    foreign_table,
+                    display_comma_separated(referred_columns)
+                )?;
+                if let Some(action) = on_delete {
+                    write!(f, " ON DELETE {}", action)?;
+                }
+                if let Some(action) = on_update {
+                    write!(f, " ON UPDATE {}", action)?;
+                }
+                Ok(())
+            }
             ColumnOption::Check(expr) => write!(f, "CHECK ({})", expr),
         }
     }
 }
 
+/// `<referential_action> =
+/// { RESTRICT | CASCADE | SET NULL | NO ACTION | SET DEFAULT }`
+///
+/// Used in foreign key constraints in `ON UPDATE` and `ON DELETE` options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferentialAction {
+    Restrict,
+    Cascade,
+    SetNull,
+    NoAction,
+    SetDefault,
+}
+
+impl Display for ReferentialAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum DataType {
     /// Fixed-length character type e.g. CHAR(10)
@@ -334,7 +425,20 @@ impl fmt::Display for DataType {
             DataType::String => write!(f, "STRING"),
             DataType::Bytea => write!(f, "BYTEA"),
             DataType::Array(ty) => write!(f, "{}[]", ty),
-            DataType::Custom(ty) => write!(f, "{}", ty),
+            // type names such as SERIAL are keywords rather than identifiers,
+            // so they are only quoted when asked to
+            DataType::Custom(ty) => {
+                for (i, part) in ty.0.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ".")?;
+                    }
+                    match part.quote_style {
+                        Some(_) => write!(f, "{}", part)?,
+                        None => write!(f, "{}", part.value)?,
+                    }
+                }
+                Ok(())
+            }
             DataType::Enum(vals) => {
                 write!(f, "ENUM(")?;
                 for (i, v) in vals.iter().enumerate() {
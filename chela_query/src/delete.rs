@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use crate::query::{Expr, ObjectName, Returning};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeleteStmt {
+    /// FROM
+    pub table_name: ObjectName,
+    /// WHERE
+    pub selection: Option<Expr>,
+    /// RETURNING
+    pub returning: Option<Returning>,
+}
+
+impl Display for DeleteStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DELETE FROM {}", self.table_name)?;
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {}", selection)?;
+        }
+        if let Some(returning) = &self.returning {
+            write!(f, " {}", returning)?;
+        }
+        Ok(())
+    }
+}
@@ -37,8 +37,6 @@ where
     DisplaySeparated { slice, sep: ", " }
 }
 
-
-
 pub struct EscapeSingleQuoteString<'a>(&'a str);
 
 impl<'a> fmt::Display for EscapeSingleQuoteString<'a> {
@@ -56,4 +54,40 @@ impl<'a> fmt::Display for EscapeSingleQuoteString<'a> {
 
 pub fn escape_single_quote_string(s: &str) -> EscapeSingleQuoteString<'_> {
     EscapeSingleQuoteString(s)
-}
\ No newline at end of file
+}
+
+/// Body of a Postgres `E'...'` literal, where backslash is the escape character
+pub struct EscapeEscapedString<'a>(&'a str);
+
+impl<'a> fmt::Display for EscapeEscapedString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\'' => write!(f, "\'\'")?,
+                '\\' => write!(f, "\\\\")?,
+                _ => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_escaped_string(s: &str) -> EscapeEscapedString<'_> {
+    EscapeEscapedString(s)
+}
+
+/// Picks a `$tag$` delimiter that cannot be mistaken for part of `s`
+pub fn dollar_quote_tag(s: &str) -> String {
+    let mut counter = 0;
+    let mut tag = String::new();
+    loop {
+        let delimiter = format!("${}$", tag);
+        // the closing delimiter must be the first match after the body, which
+        // also rules out bodies ending in a prefix of the delimiter
+        if format!("{}{}", s, delimiter).find(&delimiter) == Some(s.len()) {
+            return tag;
+        }
+        counter += 1;
+        tag = format!("q{}", counter);
+    }
+}
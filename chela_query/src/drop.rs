@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+use crate::{display::display_comma_separated, query::ObjectName};
+
+/// `DROP <object_type> [ IF EXISTS ] <name>, ... [ CASCADE | RESTRICT ]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct DropStmt {
+    pub object_type: ObjectType,
+    /// Skips missing objects instead of failing
+    pub if_exists: bool,
+    pub names: Vec<ObjectName>,
+    pub behavior: Option<DropBehavior>,
+}
+
+impl Display for DropStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DROP {} {}{}",
+            self.object_type,
+            if self.if_exists { "IF EXISTS " } else { "" },
+            display_comma_separated(&self.names)
+        )?;
+        if let Some(behavior) = &self.behavior {
+            write!(f, " {}", behavior)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObjectType {
+    Table,
+    Index,
+    View,
+    Type,
+    Sequence,
+}
+
+impl Display for ObjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ObjectType::Table => "TABLE",
+            ObjectType::Index => "INDEX",
+            ObjectType::View => "VIEW",
+            ObjectType::Type => "TYPE",
+            ObjectType::Sequence => "SEQUENCE",
+        })
+    }
+}
+
+/// What happens to the objects depending on the dropped ones
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DropBehavior {
+    /// Drops the dependent objects too
+    Cascade,
+    /// Refuses to drop while dependent objects exist, the default
+    Restrict,
+}
+
+impl Display for DropBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DropBehavior::Cascade => "CASCADE",
+            DropBehavior::Restrict => "RESTRICT",
+        })
+    }
+}
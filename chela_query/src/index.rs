@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::{
+    display::display_comma_separated,
+    query::{Expr, Ident, ObjectName, OrderByExpr},
+};
+
+/// `CREATE [ UNIQUE ] INDEX [ CONCURRENTLY ] [ IF NOT EXISTS ] <name> ON <table>
+/// [ USING <method> ] (<columns>) [ INCLUDE (<columns>) ] [ WHERE <predicate> ]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreateIndexStmt {
+    /// Postgres picks a name when None, IF NOT EXISTS requires one
+    pub name: Option<Ident>,
+    pub table_name: ObjectName,
+    pub using: Option<IndexMethod>,
+    /// Columns or expressions, expressions other than function calls
+    /// have to be nested in parentheses
+    pub columns: Vec<OrderByExpr>,
+    pub unique: bool,
+    /// Builds the index without locking out writes, not inside a transaction
+    pub concurrently: bool,
+    pub if_not_exists: bool,
+    /// Non-key columns stored in the index for index-only scans
+    pub include: Vec<Ident>,
+    /// WHERE, makes a partial index
+    pub predicate: Option<Expr>,
+}
+
+impl Display for CreateIndexStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CREATE {}INDEX {}{}",
+            if self.unique { "UNIQUE " } else { "" },
+            if self.concurrently {
+                "CONCURRENTLY "
+            } else {
+                ""
+            },
+            if self.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+        )?;
+        if let Some(name) = &self.name {
+            write!(f, "{} ", name)?;
+        }
+        write!(f, "ON {}", self.table_name)?;
+        if let Some(using) = &self.using {
+            write!(f, " USING {}", using)?;
+        }
+        write!(f, " ({})", display_comma_separated(&self.columns))?;
+        if !self.include.is_empty() {
+            write!(f, " INCLUDE ({})", display_comma_separated(&self.include))?;
+        }
+        if let Some(predicate) = &self.predicate {
+            write!(f, " WHERE {}", predicate)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IndexMethod {
+    BTree,
+    Hash,
+    Gist,
+    SpGist,
+    Gin,
+    Brin,
+}
+
+impl Display for IndexMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            IndexMethod::BTree => "btree",
+            IndexMethod::Hash => "hash",
+            IndexMethod::Gist => "gist",
+            IndexMethod::SpGist => "spgist",
+            IndexMethod::Gin => "gin",
+            IndexMethod::Brin => "brin",
+        })
+    }
+}
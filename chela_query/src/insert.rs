@@ -2,7 +2,8 @@ use std::fmt::Display;
 
 use crate::{
     display::display_comma_separated,
-    query::{Ident, QueryStmt},
+    query::{Expr, Ident, ObjectName, QueryStmt, Returning},
+    update::Assignment,
 };
 
 #[derive(Debug, PartialEq)]
@@ -12,30 +13,134 @@ pub struct InsertStmt {
     /// INTO - optional keyword
     pub into: bool,
     /// TABLE
-    pub table_name: String,
+    pub table_name: ObjectName,
     /// COLUMNS
     pub columns: Vec<Ident>,
     /// Overwrite (Hive)
     // pub overwrite: bool,
-    // A SQL query that specifies what to insert
-    pub source: QueryStmt,
+    // A SQL query that specifies what to insert, `DEFAULT VALUES` when absent
+    pub source: Option<QueryStmt>,
     // partitioned insert (Hive)
     // partitioned: Option<Vec<Expr>>,
     // Columns defined after PARTITION
     // after_columns: Vec<Ident>,
     // whether the insert has the table keyword (Hive)
     // table: bool,
-    // on: Option<OnInsert>,
+    /// ON CONFLICT
+    pub on: Option<OnInsert>,
+    /// RETURNING
+    pub returning: Option<Returning>,
 }
 
 impl Display for InsertStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "INSERT INTO {}", self.table_name)?;
+        match &self.source {
+            Some(source) if self.columns.is_empty() => write!(f, " {}", source),
+            Some(source) => write!(
+                f,
+                " ({}) {}",
+                display_comma_separated(&self.columns),
+                source
+            ),
+            // DEFAULT VALUES takes no column list
+            None => write!(f, " DEFAULT VALUES"),
+        }?;
+        if let Some(on) = &self.on {
+            write!(f, " {}", on)?;
+        }
+        if let Some(returning) = &self.returning {
+            write!(f, " {}", returning)?;
+        }
+        Ok(())
+    }
+}
+
+/// What to do when an inserted row collides with an existing one
+#[derive(Debug, PartialEq, Clone)]
+pub enum OnInsert {
+    /// `ON CONFLICT [target] DO ...`
+    OnConflict(OnConflict),
+}
+
+impl Display for OnInsert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnInsert::OnConflict(on_conflict) => write!(f, "{}", on_conflict),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OnConflict {
+    /// Required for DO UPDATE, any conflict is caught by DO NOTHING without one
+    pub conflict_target: Option<ConflictTarget>,
+    pub action: OnConflictAction,
+}
+
+impl Display for OnConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ON CONFLICT")?;
+        if let Some(conflict_target) = &self.conflict_target {
+            write!(f, " {}", conflict_target)?;
+        }
+        write!(f, " {}", self.action)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConflictTarget {
+    /// `(col, ...)`, matched against a unique index on those columns
+    Columns(Vec<Ident>),
+    /// `ON CONSTRAINT name`
+    OnConstraint(ObjectName),
+}
+
+impl Display for ConflictTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictTarget::Columns(columns) => {
+                write!(f, "({})", display_comma_separated(columns))
+            }
+            ConflictTarget::OnConstraint(name) => write!(f, "ON CONSTRAINT {}", name),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum OnConflictAction {
+    DoNothing,
+    DoUpdate(DoUpdate),
+}
+
+impl Display for OnConflictAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnConflictAction::DoNothing => write!(f, "DO NOTHING"),
+            OnConflictAction::DoUpdate(do_update) => write!(f, "{}", do_update),
+        }
+    }
+}
+
+/// `DO UPDATE SET ... [WHERE ...]`, the proposed row is reachable as `EXCLUDED`
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoUpdate {
+    /// Column assignments
+    pub assignments: Vec<Assignment>,
+    /// WHERE
+    pub selection: Option<Expr>,
+}
+
+impl Display for DoUpdate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "INSERT INTO {} ({}) {}",
-            self.table_name,
-            display_comma_separated(&self.columns),
-            self.source
-        )
+            "DO UPDATE SET {}",
+            display_comma_separated(&self.assignments)
+        )?;
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {}", selection)?;
+        }
+        Ok(())
     }
 }
@@ -1,28 +1,65 @@
+pub mod alter;
 pub mod builder;
 pub mod create;
+pub mod delete;
 pub mod display;
+pub mod drop;
+pub mod index;
 pub mod insert;
+pub mod keywords;
+pub mod operator;
+pub mod params;
 pub mod query;
+pub mod schema;
 pub mod statement;
+pub mod truncate;
+pub mod update;
 pub mod values;
 
 #[cfg(test)]
 mod tests {
+    use crate::builder::create_schema;
+    use crate::builder::excluded;
     use crate::builder::int;
+    use crate::builder::param;
+    use crate::builder::select_items;
+    use crate::builder::{avg, count, count_all, max_of, sum};
+    use crate::builder::{case, case_of, null, typed_string};
+    use crate::builder::{create_table_in, insert_into_in, select_table_in};
+    use crate::builder::{current_row, preceding, rank, row_number, unbounded_preceding, window};
+    use crate::builder::{exists, not_exists, subquery};
+    use crate::builder::{foreign_key, ForeignKeyBuilder};
+    use crate::create::ReferentialAction;
+    use crate::create::TableConstraint;
     use crate::create::TableConstraint::ForeignKey;
+    use crate::params::CollectParams;
+    use crate::query::LockType;
+    use crate::statement::Statement;
+    use crate::{alter::AlterColumnOperation, builder::alter_table};
     use crate::{
         builder::{
-            create_table, not_null, primary_key_unique, select_table, serial, varchar,
-            ColumnOptionDefBuilder, CreateBuilder, DataTypeBuilder, QueryBuilder,
+            col, create_table, delete_from, insert_into, not, not_null, primary_key_unique,
+            select_table, serial, table, table_as, update, varchar, QueryBuilder,
         },
         create::{ColumnDef, ColumnOption, ColumnOptionDef, CreateStmt, DataType},
+        delete::DeleteStmt,
         insert::InsertStmt,
-        query::QueryStmt,
+        operator::{BinaryOperator, UnaryOperator},
         query::{Expr, Select},
-        query::{Ident, ObjectName, SelectItem, TableWithJoins},
+        query::{Ident, ObjectName, Returning, SelectItem, TableWithJoins},
+        query::{OrderByExpr, QueryStmt},
         query::{SetExpr, TableFactor},
+        update::{Assignment, UpdateStmt},
         values::{Value, Values},
     };
+    use crate::{
+        builder::{create_index, create_index_in, function},
+        index::IndexMethod,
+    };
+    use crate::{
+        builder::{drop_index, drop_table, drop_table_in, truncate, DropBuilder},
+        drop::ObjectType,
+    };
 
     #[test]
     fn create_table_fkey_test() {
@@ -45,26 +82,20 @@ mod tests {
 
     fn create_fkey_stmt() -> CreateStmt {
         CreateStmt {
-            name: ObjectName(vec![Ident {
-                value: "article".to_string(),
-            }]),
+            persistence: None,
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("article".to_string())]),
             columns: vec![
                 ColumnDef {
-                    name: Ident {
-                        value: "id".to_string(),
-                    },
-                    data_type: DataType::Custom(ObjectName(vec![Ident {
-                        value: "SERIAL".to_string(),
-                    }])),
+                    name: Ident::new("id".to_string()),
+                    data_type: DataType::Custom(ObjectName(vec![Ident::new("SERIAL".to_string())])),
                     options: vec![ColumnOptionDef {
                         name: None,
                         option: ColumnOption::Unique { is_primary: true },
                     }],
                 },
                 ColumnDef {
-                    name: Ident {
-                        value: "author_id".to_string(),
-                    },
+                    name: Ident::new("author_id".to_string()),
                     data_type: DataType::Int(None),
                     options: vec![ColumnOptionDef {
                         name: None,
@@ -73,20 +104,13 @@ mod tests {
                 },
             ],
             constraints: vec![ForeignKey {
-                name: Some(Ident {
-                    value: "fk_author".to_string(),
-                }),
-                columns: vec![Ident {
-                    value: "author_id".to_string(),
-                }],
-                foreign_table: ObjectName(vec![Ident {
-                    value: "author".to_string(),
-                }]),
-                referred_columns: vec![Ident {
-                    value: "id".to_string(),
-                }],
-                // on_delete: None,
-                // on_update: None,
+                name: Some(Ident::new("fk_author".to_string())),
+                columns: vec![Ident::new("author_id".to_string())],
+                foreign_table: ObjectName(vec![Ident::new("author".to_string())]),
+                referred_columns: vec![Ident::new("id".to_string())],
+                on_delete: None,
+                on_update: None,
+                deferrable: false,
             }],
             // primary_key: Some(vec![Ident{value: "id".to_string()}]),
             // ..Default::default()
@@ -108,28 +132,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_table_options_test() {
+        let query = create_table("order_lines".to_string(), vec![])
+            .column("order_id".to_string(), DataType::Int(None), not_null())
+            .column("line".to_string(), DataType::Int(None), not_null())
+            .column("sku".to_string(), varchar(Some(32)), not_null())
+            .if_not_exists()
+            .primary_key_constraint(vec!["order_id".to_string(), "line".to_string()])
+            .unique_constraint(vec!["order_id".to_string(), "sku".to_string()])
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE IF NOT EXISTS order_lines (order_id INT NOT NULL, line INT NOT NULL, \
+             sku VARCHAR(32) NOT NULL, PRIMARY KEY (order_id, line), UNIQUE (order_id, sku))"
+        );
+
+        let query = create_table("scratch".to_string(), vec![])
+            .column("id".to_string(), serial(), primary_key_unique())
+            .temporary()
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "CREATE TEMPORARY TABLE scratch (id SERIAL PRIMARY KEY)"
+        );
+        // rejected when the combination is set, in either order
+        assert!(std::panic::catch_unwind(|| create_table_in(
+            "app".to_string(),
+            "scratch".to_string(),
+            vec![]
+        )
+        .temporary())
+        .is_err());
+        assert!(
+            std::panic::catch_unwind(|| create_table("scratch".to_string(), vec![])
+                .temporary()
+                .name_in("app".to_string(), "scratch".to_string()))
+            .is_err()
+        );
+
+        let query = create_table("events".to_string(), vec![])
+            .column("id".to_string(), serial(), primary_key_unique())
+            .unlogged()
+            .constraint(TableConstraint::Unique {
+                name: Some(Ident::new("events_id_key")),
+                columns: vec![Ident::new("id")],
+                is_primary: false,
+            })
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "CREATE UNLOGGED TABLE events (id SERIAL PRIMARY KEY, \
+             CONSTRAINT events_id_key UNIQUE (id))"
+        );
+    }
+
+    #[test]
+    fn foreign_key_actions_test() {
+        let query = create_table("article".to_string(), vec![])
+            .column("id".to_string(), serial(), primary_key_unique())
+            .column("author_id".to_string(), DataType::Int(None), not_null())
+            .constraint(
+                foreign_key(
+                    "fk_author".to_string(),
+                    "author_id".to_string(),
+                    "author".to_string(),
+                    "id".to_string(),
+                )
+                .on_delete(ReferentialAction::Cascade)
+                .on_update(ReferentialAction::NoAction)
+                .build(),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "CREATE TABLE article (id SERIAL PRIMARY KEY, author_id INT NOT NULL, \
+             CONSTRAINT fk_author FOREIGN KEY (author_id) REFERENCES author (id) \
+             ON DELETE CASCADE ON UPDATE NO ACTION)"
+        );
+
+        let query = alter_table("article".to_string())
+            .add_constraint(
+                ForeignKeyBuilder::new(
+                    vec!["editor_id".to_string()],
+                    ObjectName::qualified("staff".to_string(), "editor".to_string()),
+                    vec!["id".to_string()],
+                )
+                .on_delete(ReferentialAction::SetNull)
+                .deferrable()
+                .build(),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "ALTER TABLE article ADD FOREIGN KEY (editor_id) REFERENCES staff.editor (id) \
+             ON DELETE SET NULL DEFERRABLE INITIALLY DEFERRED"
+        );
+
+        let option = ColumnOption::ForeignKey {
+            foreign_table: ObjectName(vec![Ident::new("author")]),
+            referred_columns: vec![Ident::new("id")],
+            on_delete: Some(ReferentialAction::SetDefault),
+            on_update: Some(ReferentialAction::Restrict),
+        };
+        assert_eq!(
+            option.to_string(),
+            "REFERENCES author (id) ON DELETE SET DEFAULT ON UPDATE RESTRICT"
+        );
+    }
+
     fn create_stmt() -> CreateStmt {
         CreateStmt {
-            name: ObjectName(vec![Ident {
-                value: "alphabet".to_string(),
-            }]),
+            persistence: None,
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("alphabet".to_string())]),
             columns: vec![
                 ColumnDef {
-                    name: Ident {
-                        value: "id".to_string(),
-                    },
-                    data_type: DataType::Custom(ObjectName(vec![Ident {
-                        value: "SERIAL".to_string(),
-                    }])),
+                    name: Ident::new("id".to_string()),
+                    data_type: DataType::Custom(ObjectName(vec![Ident::new("SERIAL".to_string())])),
                     options: vec![ColumnOptionDef {
                         name: None,
                         option: ColumnOption::Unique { is_primary: true },
                     }],
                 },
                 ColumnDef {
-                    name: Ident {
-                        value: "letter".to_string(),
-                    },
+                    name: Ident::new("letter".to_string()),
                     data_type: DataType::Varchar(None),
                     options: vec![ColumnOptionDef {
                         name: None,
@@ -147,27 +274,27 @@ mod tests {
     fn insert_test() {
         let i = InsertStmt {
             into: true,
-            table_name: "links".to_string(),
+            table_name: ObjectName(vec![Ident::new("links".to_string())]),
             columns: vec![
-                Ident {
-                    value: "url".to_string(),
-                },
-                Ident {
-                    value: "name".to_string(),
-                },
+                Ident::new("url".to_string()),
+                Ident::new("name".to_string()),
             ],
-            source: QueryStmt {
+            source: Some(QueryStmt {
+                with: None,
                 body: SetExpr::Values(Values(vec![vec![
                     Expr::Value(Value::SingleQuotedString(
                         "https://www.postgresqltutorial.com".to_string(),
                     )),
-                    Expr::Value(Value::SingleQuotedString(
-                        "PostgreSQL Tutorial".to_string(),
-                    )),
+                    Expr::Value(Value::SingleQuotedString("PostgreSQL Tutorial".to_string())),
                 ]])),
-                order_by: None,
+                order_by: vec![],
                 limit: None,
-            },
+                offset: None,
+                fetch: None,
+                locks: vec![],
+            }),
+            on: None,
+            returning: None,
         };
         assert_eq!(
             i.to_string(),
@@ -176,6 +303,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_test() {
+        let u = UpdateStmt {
+            table_name: ObjectName(vec![Ident::new("links".to_string())]),
+            assignments: vec![Assignment {
+                id: Ident::new("name".to_string()),
+                value: Expr::Value(Value::SingleQuotedString("PostgreSQL".to_string())),
+            }],
+            selection: Some(Expr::InList {
+                expr: Box::new(Expr::Identifier(Ident::new("id".to_string()))),
+                list: vec![Expr::Value(Value::Number("1".to_string(), false))],
+                negated: false,
+            }),
+            returning: Some(Returning(vec![SelectItem::Wildcard])),
+        };
+        let built_update = update("links".to_string())
+            .set(
+                "name".to_string(),
+                Expr::Value(Value::SingleQuotedString("PostgreSQL".to_string())),
+            )
+            .selection(Expr::InList {
+                expr: Box::new(Expr::Identifier(Ident::new("id".to_string()))),
+                list: vec![Expr::Value(Value::Number("1".to_string(), false))],
+                negated: false,
+            })
+            .returning(vec![SelectItem::Wildcard])
+            .build();
+        assert_eq!(built_update, u);
+        assert_eq!(
+            u.to_string(),
+            "UPDATE links SET name = 'PostgreSQL' WHERE id IN (1) RETURNING *"
+        );
+    }
+
+    #[test]
+    fn delete_test() {
+        let d = DeleteStmt {
+            table_name: ObjectName(vec![Ident::new("orders".to_string())]),
+            selection: Some(Expr::InList {
+                expr: Box::new(Expr::Identifier(Ident::new("user_id".to_string()))),
+                list: vec![
+                    Expr::Value(Value::Number("1".to_string(), false)),
+                    Expr::Value(Value::Number("2".to_string(), false)),
+                ],
+                negated: false,
+            }),
+            returning: Some(Returning(vec![SelectItem::Wildcard])),
+        };
+        let built_delete = delete_from("orders".to_string())
+            .selection(Expr::InList {
+                expr: Box::new(Expr::Identifier(Ident::new("user_id".to_string()))),
+                list: vec![
+                    Expr::Value(Value::Number("1".to_string(), false)),
+                    Expr::Value(Value::Number("2".to_string(), false)),
+                ],
+                negated: false,
+            })
+            .returning(vec![SelectItem::Wildcard])
+            .build();
+        assert_eq!(built_delete, d);
+        assert_eq!(
+            d.to_string(),
+            "DELETE FROM orders WHERE user_id IN (1, 2) RETURNING *"
+        );
+    }
+
     #[test]
     fn query_in_list_test() {
         let orders_query = find_orders_in_list();
@@ -199,21 +392,983 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_selection_test() {
+        let query = select_table("users".to_string())
+            .selection(col("age").gt(18).and(col("name").like("a%")))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM users WHERE age > 18 AND name LIKE 'a%'"
+        );
+        let selection = col("age").gt(18);
+        assert_eq!(
+            selection,
+            Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("age".to_string()))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("18".to_string(), false))),
+            }
+        );
+    }
+
+    #[test]
+    fn expr_nesting_test() {
+        let expr = col("a").eq(1).or(col("b").eq(2)).and(col("c").is_null());
+        assert_eq!(expr.to_string(), "(a = 1 OR b = 2) AND c IS NULL");
+
+        let expr = not(col("a").eq(1).and(col("b").not_eq(2)));
+        assert_eq!(expr.to_string(), "NOT (a = 1 AND b <> 2)");
+
+        let expr = Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr: Box::new(col("a")),
+        }
+        .double_colon(DataType::Text);
+        assert_eq!(expr.to_string(), "(-a)::TEXT");
+
+        let expr = Expr::Value(Value::Number("5".to_string(), true)).double_colon(DataType::Text);
+        assert_eq!(expr.to_string(), "(-5)::TEXT");
+
+        let expr = col("age")
+            .not_between(18, 65)
+            .or(col("email").ilike("%@example.com").nested())
+            .or(col("deleted_at").is_not_null());
+        assert_eq!(
+            expr.to_string(),
+            "age NOT BETWEEN 18 AND 65 OR (email ILIKE '%@example.com') OR deleted_at IS NOT NULL"
+        );
+
+        let expr = !col("id").in_list(vec![1, 2]);
+        assert_eq!(expr.to_string(), "NOT id IN (1, 2)");
+
+        let expr = col("score")
+            .gt(f64::NEG_INFINITY)
+            .and(col("score").lt(f64::INFINITY))
+            .and(col("score").not_eq(f64::NAN));
+        assert_eq!(
+            expr.to_string(),
+            "score > '-Infinity'::float8 AND score < 'Infinity'::float8 AND score <> 'NaN'::float8"
+        );
+    }
+
+    #[test]
+    fn query_join_test() {
+        let query = QueryBuilder::new()
+            .select()
+            .from_as("orders".to_string(), "o".to_string())
+            .join(table_as("users", "u"), col("u.id").eq(col("o.user_id")))
+            .left_join(table("payments"), col("payments.order_id").eq(col("o.id")))
+            .cross_join(table("currencies"))
+            .selection(col("u.id").eq(1))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM orders AS o JOIN users AS u ON u.id = o.user_id LEFT JOIN payments ON payments.order_id = o.id CROSS JOIN currencies WHERE u.id = 1"
+        );
+
+        let query = select_table("orders".to_string())
+            .join_using(table("users"), vec!["user_id".to_string()])
+            .full_join(
+                table("refunds"),
+                col("refunds.order_id").eq(col("orders.id")),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM orders JOIN users USING (user_id) FULL JOIN refunds ON refunds.order_id = orders.id"
+        );
+
+        let query = QueryBuilder::new()
+            .select()
+            .join(table("users"), col("users.id").eq(col("orders.user_id")))
+            .from("orders".to_string())
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM orders JOIN users ON users.id = orders.user_id"
+        );
+
+        // a join needs a FROM table to join to
+        assert!(std::panic::catch_unwind(|| QueryBuilder::new()
+            .select()
+            .join(table("users"), col("users.active"))
+            .build())
+        .is_err());
+    }
+
+    #[test]
+    fn query_projection_test() {
+        let query = select_table("users".to_string())
+            .columns(["id", "username"])
+            .build();
+        assert_eq!(query.to_string(), "SELECT id, username FROM users");
+
+        let query = QueryBuilder::new()
+            .from_as("orders".to_string(), "o".to_string())
+            .join(table_as("users", "u"), col("u.id").eq(col("o.user_id")))
+            .projection(vec![
+                SelectItem::QualifiedWildcard(ObjectName(vec![Ident::new("o".to_string())])),
+                SelectItem::ExprWithAlias {
+                    expr: col("u.username"),
+                    alias: Ident::new("author".to_string()),
+                },
+            ])
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT o.*, u.username AS author FROM orders AS o JOIN users AS u ON u.id = o.user_id"
+        );
+    }
+
+    #[test]
+    fn query_order_by_offset_test() {
+        let query = select_table("users".to_string())
+            .order_by_expr(col("last_login").desc().nulls_last())
+            .order_by_expr(col("id").asc())
+            .limit(Some(20))
+            .offset(Some(40))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM users ORDER BY last_login DESC NULLS LAST, id ASC LIMIT 20 OFFSET 40"
+        );
+
+        let query = select_table("scores".to_string())
+            .order_by_expr(col("points").desc())
+            .offset(Some(10))
+            .fetch_with_ties(Some(3))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM scores ORDER BY points DESC OFFSET 10 FETCH FIRST 3 ROWS WITH TIES"
+        );
+
+        let query = select_table("scores".to_string())
+            .limit(Some(5))
+            .fetch(Some(3))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM scores FETCH FIRST 3 ROWS ONLY"
+        );
+
+        let query = select_table("scores".to_string())
+            .fetch(Some(3))
+            .limit(Some(5))
+            .build();
+        assert_eq!(query.to_string(), "SELECT * FROM scores LIMIT 5");
+    }
+
+    #[test]
+    fn bind_params_test() {
+        let statement = select_table("users".to_string())
+            .selection(
+                col("name")
+                    .eq(param("o'brien".to_string()))
+                    .and(col("age").gt_eq(param(18))),
+            )
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT * FROM users WHERE name = $1 AND age >= $2"
+        );
+        assert_eq!(format!("{:?}", statement.params()), "[\"o'brien\", 18]");
+
+        let statement = select_table("orders".to_string())
+            .where_("user_id".to_string())
+            .bind_in_list(vec![1, 2, 3])
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT * FROM orders WHERE user_id IN ($1, $2, $3)"
+        );
+        assert_eq!(statement.params().len(), 3);
+
+        let statement = insert_into("orders".to_string())
+            .columns(vec!["user_id".to_string(), "price".to_string()])
+            .bind_values(vec![Box::new(1), Box::new(9.99)])
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO orders (user_id, price) VALUES ($1, $2)"
+        );
+        assert_eq!(format!("{:?}", statement.params()), "[1, 9.99]");
+    }
+
+    #[test]
+    fn ident_quoting_test() {
+        assert_eq!(Ident::new("orders").to_string(), "orders");
+        assert_eq!(Ident::new("order").to_string(), "\"order\"");
+        assert_eq!(Ident::new("user").to_string(), "\"user\"");
+        assert_eq!(Ident::new("createdAt").to_string(), "\"createdAt\"");
+        assert_eq!(Ident::new("1st").to_string(), "\"1st\"");
+        assert_eq!(Ident::new("a\"b").to_string(), "\"a\"\"b\"");
+        assert_eq!(Ident::with_quote('"', "id").to_string(), "\"id\"");
+        assert_eq!(Ident::with_quote('"', "A\"b").to_string(), "\"A\"\"b\"");
+        assert!(std::panic::catch_unwind(|| Ident::with_quote('`', "id")).is_err());
+
+        let query = select_table("user".to_string())
+            .columns(["id", "order"])
+            .selection(col("user.order").eq(1))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT id, \"order\" FROM \"user\" WHERE \"user\".\"order\" = 1"
+        );
+
+        let statement = insert_into("user".to_string())
+            .columns(vec!["order".to_string()])
+            .bind_values(vec![Box::new(1)])
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO \"user\" (\"order\") VALUES ($1)"
+        );
+    }
+
+    #[test]
+    fn schema_qualified_name_test() {
+        let create = create_table_in("billing".to_string(), "invoices".to_string(), vec![])
+            .column("id".to_string(), serial(), primary_key_unique())
+            .build();
+        assert_eq!(
+            create.to_string(),
+            "CREATE TABLE billing.invoices (id SERIAL PRIMARY KEY)"
+        );
+
+        let query = select_table_in("billing".to_string(), "User".to_string()).build();
+        assert_eq!(query.to_string(), "SELECT * FROM billing.\"User\"");
+
+        let statement = insert_into_in("billing".to_string(), "invoices".to_string())
+            .columns(vec!["id".to_string()])
+            .bind_values(vec![Box::new(1)])
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO billing.invoices (id) VALUES ($1)"
+        );
+
+        let create = create_table("payments".to_string(), vec![])
+            .column("invoice_id".to_string(), int(None), not_null())
+            .foreign_key_constraint_in(
+                "fk_invoice".to_string(),
+                "invoice_id".to_string(),
+                "billing".to_string(),
+                "invoices".to_string(),
+                "id".to_string(),
+            )
+            .build();
+        assert_eq!(
+            create.to_string(),
+            "CREATE TABLE payments (invoice_id INT NOT NULL, \
+             CONSTRAINT fk_invoice FOREIGN KEY (invoice_id) REFERENCES billing.invoices (id))"
+        );
+
+        let create = create_schema("billing".to_string()).if_not_exists().build();
+        assert_eq!(create.to_string(), "CREATE SCHEMA IF NOT EXISTS billing");
+    }
+
+    #[test]
+    fn statement_batch_test() {
+        let statements = vec![
+            Statement::CreateStmt(create_stmt()),
+            Statement::InsertStmt(
+                insert_into("alphabet".to_string())
+                    .columns(vec!["letter".to_string()])
+                    .values(vec!["a".to_string()])
+                    .build(),
+            ),
+            Statement::QueryStmt(select_table("alphabet".to_string()).build()),
+        ];
+        assert_eq!(
+            Statement::to_sql_batch(&statements),
+            "CREATE TABLE alphabet (id SERIAL PRIMARY KEY, letter VARCHAR NOT NULL);\n\
+             INSERT INTO alphabet (letter) VALUES ('a');\n\
+             SELECT * FROM alphabet;"
+        );
+    }
+
+    #[test]
+    fn insert_rows_test() {
+        let statement = insert_into("orders".to_string())
+            .columns(vec!["user_id".to_string(), "price".to_string()])
+            .bind_row(vec![Box::new(1), Box::new(9.99)])
+            .bind_row(vec![Box::new(2), Box::new(4.5)])
+            .row(vec![Expr::from(3), Expr::from(0.5)])
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO orders (user_id, price) VALUES ($1, $2), ($3, $4), (3, 0.5)"
+        );
+        assert_eq!(statement.params().len(), 4);
+
+        let archived = select_table("orders".to_string())
+            .columns(["user_id", "price"])
+            .selection(col("price").gt(param(100)));
+        let statement = insert_into("archived_orders".to_string())
+            .columns(vec!["user_id".to_string(), "price".to_string()])
+            .select(archived)
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO archived_orders (user_id, price) SELECT user_id, price FROM orders WHERE price > $1"
+        );
+        assert_eq!(statement.params().len(), 1);
+
+        let insert = insert_into("counters".to_string()).build();
+        assert_eq!(insert.to_string(), "INSERT INTO counters DEFAULT VALUES");
+        let insert = insert_into("counters".to_string())
+            .columns(vec!["name".to_string()])
+            .values(vec!["a".to_string()])
+            .default_values()
+            .build();
+        assert_eq!(insert.to_string(), "INSERT INTO counters DEFAULT VALUES");
+        assert!(insert.columns.is_empty());
+        let insert = insert_into("counters".to_string())
+            .columns(vec!["name".to_string()])
+            .build();
+        assert_eq!(insert.to_string(), "INSERT INTO counters DEFAULT VALUES");
+
+        let statement = insert_into("archived_orders".to_string())
+            .columns(vec!["user_id".to_string(), "price".to_string()])
+            .select(
+                select_table("orders".to_string())
+                    .columns(["user_id", "price"])
+                    .selection(col("price").gt(param(100))),
+            )
+            .on_conflict(vec!["user_id".to_string()])
+            .do_update_set("price".to_string(), param(0))
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO archived_orders (user_id, price) SELECT user_id, price FROM orders WHERE price > $1 \
+             ON CONFLICT (user_id) DO UPDATE SET price = $2"
+        );
+        assert_eq!(format!("{:?}", statement.params()), "[100, 0]");
+    }
+
+    #[test]
+    fn upsert_test() {
+        let insert = insert_into("feeds".to_string())
+            .columns(vec!["url".to_string(), "title".to_string()])
+            .values(vec!["https://a.example".to_string(), "A".to_string()])
+            .on_conflict(vec!["url".to_string()])
+            .build();
+        assert_eq!(
+            insert.to_string(),
+            "INSERT INTO feeds (url, title) VALUES ('https://a.example', 'A') ON CONFLICT (url) DO NOTHING"
+        );
+
+        let insert = insert_into("feeds".to_string())
+            .columns(vec!["url".to_string()])
+            .values(vec!["https://a.example".to_string()])
+            .do_nothing()
+            .build();
+        assert_eq!(
+            insert.to_string(),
+            "INSERT INTO feeds (url) VALUES ('https://a.example') ON CONFLICT DO NOTHING"
+        );
+
+        let statement = insert_into("feeds".to_string())
+            .columns(vec![
+                "url".to_string(),
+                "title".to_string(),
+                "hits".to_string(),
+            ])
+            .bind_row(vec![
+                Box::new("https://a.example"),
+                Box::new("A"),
+                Box::new(1),
+            ])
+            .on_conflict_on_constraint("feeds_url_key".to_string())
+            .do_update_set_excluded(vec!["title".to_string()])
+            .do_update_set("hits".to_string(), excluded("hits"))
+            .do_update_where(col("feeds.title").not_eq(excluded("title")))
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO feeds (url, title, hits) VALUES ($1, $2, $3) \
+             ON CONFLICT ON CONSTRAINT feeds_url_key \
+             DO UPDATE SET title = excluded.title, hits = excluded.hits \
+             WHERE feeds.title <> excluded.title"
+        );
+        assert_eq!(statement.params().len(), 3);
+    }
+
+    #[test]
+    fn returning_test() {
+        let statement = insert_into("user".to_string())
+            .columns(vec!["username".to_string()])
+            .bind_values(vec![Box::new("john")])
+            .returning(select_items(["id", "username"]))
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO \"user\" (username) VALUES ($1) RETURNING id, username"
+        );
+
+        let insert = insert_into("feeds".to_string())
+            .columns(vec!["url".to_string()])
+            .values(vec!["https://a.example".to_string()])
+            .do_nothing()
+            .returning(vec![SelectItem::Wildcard])
+            .build();
+        assert_eq!(
+            insert.to_string(),
+            "INSERT INTO feeds (url) VALUES ('https://a.example') ON CONFLICT DO NOTHING RETURNING *"
+        );
+
+        let update = update("feeds".to_string())
+            .set("title".to_string(), Expr::from("B"))
+            .returning(select_items(["id"]))
+            .build();
+        assert_eq!(
+            update.to_string(),
+            "UPDATE feeds SET title = 'B' RETURNING id"
+        );
+
+        let delete = delete_from("feeds".to_string())
+            .returning(select_items(["id"]))
+            .build();
+        assert_eq!(delete.to_string(), "DELETE FROM feeds RETURNING id");
+    }
+
+    #[test]
+    fn aggregate_test() {
+        let report = select_table("orders".to_string())
+            .projection(vec![
+                SelectItem::UnnamedExpr(col("user_id")),
+                SelectItem::ExprWithAlias {
+                    expr: count_all().build(),
+                    alias: Ident::new("orders"),
+                },
+                SelectItem::UnnamedExpr(count(col("product_id")).distinct().build()),
+                SelectItem::UnnamedExpr(sum(col("price")).filter(col("paid").eq(1)).build()),
+                SelectItem::UnnamedExpr(avg(col("price")).build()),
+                SelectItem::UnnamedExpr(max_of(col("price")).build()),
+            ])
+            .group_by(vec![col("user_id")])
+            .having(
+                sum(col("price"))
+                    .build()
+                    .gt(param(1000.0))
+                    .and(count_all().build().gt(2)),
+            )
+            .build();
+        assert_eq!(
+            report.to_string(),
+            "SELECT user_id, count(*) AS orders, count(DISTINCT product_id), \
+             sum(price) FILTER (WHERE paid = 1), avg(price), max(price) \
+             FROM orders GROUP BY user_id HAVING sum(price) > $1 AND count(*) > 2"
+        );
+    }
+
+    #[test]
+    fn subquery_test() {
+        let paid = select_table("orders".to_string())
+            .columns(["user_id"])
+            .selection(col("price").gt(param(100)));
+        let statement = select_table("users".to_string())
+            .selection(col("id").in_subquery(paid.clone()))
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT * FROM users WHERE id IN (SELECT user_id FROM orders WHERE price > $1)"
+        );
+        assert_eq!(statement.params().len(), 1);
+
+        // values bound on both sides are numbered in render order
+        let statement = select_table("users".to_string())
+            .selection(col("age").gt(param(18)).and(col("id").in_subquery(paid)))
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT * FROM users WHERE age > $1 AND id IN (SELECT user_id FROM orders WHERE price > $2)"
+        );
+        assert_eq!(format!("{:?}", statement.params()), "[18, 100]");
+
+        // SORT BY is not rendered, so a value bound there takes no placeholder
+        let mut sorted = select_table("users".to_string()).selection(col("age").gt(param(18)));
+        sorted.sort_by = vec![param(0)];
+        let statement = sorted.build();
+        assert_eq!(statement.to_string(), "SELECT * FROM users WHERE age > $1");
+        assert_eq!(format!("{:?}", statement.params()), "[18]");
+
+        let orders_of_user =
+            select_table("orders".to_string()).selection(col("orders.user_id").eq(col("users.id")));
+        let query = select_table("users".to_string())
+            .selection(
+                exists(orders_of_user.clone()).and(not(col("id").not_in_subquery(orders_of_user))),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM users WHERE EXISTS (SELECT * FROM orders WHERE orders.user_id = users.id) \
+             AND NOT id NOT IN (SELECT * FROM orders WHERE orders.user_id = users.id)"
+        );
+
+        let query = select_table("users".to_string())
+            .selection(not_exists(select_table("orders".to_string())))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM users WHERE NOT EXISTS (SELECT * FROM orders)"
+        );
+
+        let newest = select_table("orders".to_string())
+            .projection(vec![SelectItem::UnnamedExpr(max_of(col("id")).build())]);
+        let query = select_table("orders".to_string())
+            .selection(col("id").eq(subquery(newest)))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM orders WHERE id = (SELECT max(id) FROM orders)"
+        );
+
+        let totals = select_table("orders".to_string())
+            .projection(vec![
+                SelectItem::UnnamedExpr(col("user_id")),
+                SelectItem::ExprWithAlias {
+                    expr: sum(col("price")).build(),
+                    alias: Ident::new("total"),
+                },
+            ])
+            .group_by(vec![col("user_id")]);
+        let query = QueryBuilder::new()
+            .select()
+            .from_subquery(totals, "totals".to_string())
+            .selection(col("totals.total").gt(100))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM (SELECT user_id, sum(price) AS total FROM orders GROUP BY user_id) AS totals \
+             WHERE totals.total > 100"
+        );
+    }
+
+    #[test]
+    fn with_test() {
+        let roots = select_table("categories".to_string())
+            .columns(["id", "parent_id"])
+            .selection(col("parent_id").is_null());
+        let query = select_table("tree".to_string())
+            .with_recursive(
+                "tree".to_string(),
+                vec!["id".to_string(), "parent_id".to_string()],
+                roots,
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "WITH RECURSIVE tree (id, parent_id) AS \
+             (SELECT id, parent_id FROM categories WHERE parent_id IS NULL) SELECT * FROM tree"
+        );
+
+        let query = select_table("big_spenders".to_string())
+            .with(
+                "paid".to_string(),
+                select_table("orders".to_string()).selection(col("paid").eq(1)),
+            )
+            .with(
+                "big_spenders".to_string(),
+                select_table("paid".to_string()).selection(col("price").gt(100)),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "WITH paid AS (SELECT * FROM orders WHERE paid = 1), \
+             big_spenders AS (SELECT * FROM paid WHERE price > 100) SELECT * FROM big_spenders"
+        );
+    }
+
+    #[test]
+    fn set_operation_test() {
+        let customers = || select_table("customers".to_string()).columns(["email"]);
+        let query = customers()
+            .union(select_table("leads".to_string()).columns(["email"]))
+            .order_by(Some("email".to_string()))
+            .limit(Some(10))
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT email FROM customers UNION SELECT email FROM leads ORDER BY email LIMIT 10"
+        );
+
+        let query = customers()
+            .except_all(
+                select_table("unsubscribed".to_string())
+                    .columns(["email"])
+                    .order_by(Some("email".to_string()))
+                    .limit(Some(5)),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT email FROM customers EXCEPT ALL \
+             (SELECT email FROM unsubscribed ORDER BY email LIMIT 5)"
+        );
+
+        let leads = || select_table("leads".to_string()).columns(["email"]);
+        let staff = || select_table("staff".to_string()).columns(["email"]);
+        let query = customers().union(leads()).union(staff()).build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT email FROM customers UNION SELECT email FROM leads UNION SELECT email FROM staff"
+        );
+        // INTERSECT binds tighter, so only the grouping against that needs parentheses
+        let query = customers().union(leads().intersect(staff())).build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT email FROM customers UNION SELECT email FROM leads INTERSECT SELECT email FROM staff"
+        );
+        let query = customers().union(leads()).intersect_all(staff()).build();
+        assert_eq!(
+            query.to_string(),
+            "(SELECT email FROM customers UNION SELECT email FROM leads) \
+             INTERSECT ALL SELECT email FROM staff"
+        );
+        let query = customers().except(leads().union_all(staff())).build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT email FROM customers EXCEPT \
+             (SELECT email FROM leads UNION ALL SELECT email FROM staff)"
+        );
+        // WHERE after a set operation has no SELECT to apply to
+        assert!(std::panic::catch_unwind(|| customers()
+            .union(leads())
+            .selection(col("email").is_null())
+            .build())
+        .is_err());
+    }
+
+    #[test]
+    fn recursive_cte_test() {
+        let tree = select_table("categories".to_string())
+            .columns(["id", "parent_id"])
+            .selection(col("parent_id").is_null())
+            .union_all(
+                select_table("categories".to_string())
+                    .projection(select_items(["c.id", "c.parent_id"]))
+                    .from_as("categories".to_string(), "c".to_string())
+                    .join(table("tree"), col("tree.id").eq(col("c.parent_id"))),
+            );
+        let query = select_table("tree".to_string())
+            .with_recursive(
+                "tree".to_string(),
+                vec!["id".to_string(), "parent_id".to_string()],
+                tree,
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "WITH RECURSIVE tree (id, parent_id) AS \
+             (SELECT id, parent_id FROM categories WHERE parent_id IS NULL \
+             UNION ALL SELECT c.id, c.parent_id FROM categories AS c JOIN tree ON tree.id = c.parent_id) \
+             SELECT * FROM tree"
+        );
+    }
+
+    #[test]
+    fn window_function_test() {
+        let query = select_table("orders".to_string())
+            .projection(vec![
+                SelectItem::UnnamedExpr(col("id")),
+                SelectItem::ExprWithAlias {
+                    expr: rank()
+                        .over(
+                            window()
+                                .partition_by(vec![col("user_id")])
+                                .order_by(vec![col("price").desc()]),
+                        )
+                        .build(),
+                    alias: Ident::new("price_rank"),
+                },
+                SelectItem::ExprWithAlias {
+                    expr: sum(col("price")).over_window("running").build(),
+                    alias: Ident::new("running_total"),
+                },
+                SelectItem::UnnamedExpr(
+                    avg(col("price"))
+                        .over(
+                            window()
+                                .order_by(vec![col("created_at").asc()])
+                                .rows_between(preceding(2), current_row()),
+                        )
+                        .build(),
+                ),
+                SelectItem::UnnamedExpr(row_number().over(window()).build()),
+            ])
+            .window(
+                "running".to_string(),
+                window()
+                    .partition_by(vec![col("user_id")])
+                    .order_by(vec![col("created_at").asc()])
+                    .range_between(unbounded_preceding(), current_row()),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT id, rank() OVER (PARTITION BY user_id ORDER BY price DESC) AS price_rank, \
+             sum(price) OVER running AS running_total, \
+             avg(price) OVER (ORDER BY created_at ASC ROWS BETWEEN 2 PRECEDING AND CURRENT ROW), \
+             row_number() OVER () FROM orders \
+             WINDOW running AS (PARTITION BY user_id ORDER BY created_at ASC \
+             RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)"
+        );
+    }
+
+    #[test]
+    fn case_cast_literal_test() {
+        let query = select_table("orders".to_string())
+            .projection(vec![
+                SelectItem::ExprWithAlias {
+                    expr: case(col("price").gt(100), "large")
+                        .when(col("price").is_null(), null())
+                        .else_("small")
+                        .end(),
+                    alias: Ident::new("size"),
+                },
+                SelectItem::UnnamedExpr(case_of(col("status"), 1, true).when(2, false).end()),
+                SelectItem::UnnamedExpr(col("price").cast(DataType::Decimal(Some(10), Some(2)))),
+                SelectItem::UnnamedExpr(
+                    Expr::BinaryOp {
+                        left: Box::new(col("price")),
+                        op: BinaryOperator::Plus,
+                        right: Box::new(Expr::from(1)),
+                    }
+                    .double_colon(DataType::Text),
+                ),
+                SelectItem::UnnamedExpr(col("id").double_colon(DataType::Text)),
+                SelectItem::UnnamedExpr(Expr::from(-1).double_colon(DataType::Text)),
+            ])
+            .selection(
+                col("created_at")
+                    .gt_eq(typed_string(DataType::Date, "2020-01-01"))
+                    .and(col("paid").eq(true)),
+            )
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT CASE WHEN price > 100 THEN 'large' WHEN price IS NULL THEN NULL ELSE 'small' END AS size, \
+             CASE status WHEN 1 THEN TRUE WHEN 2 THEN FALSE END, \
+             CAST(price AS NUMERIC(10,2)), (price + 1)::TEXT, id::TEXT, (-1)::TEXT \
+             FROM orders WHERE created_at >= DATE '2020-01-01' AND paid = TRUE"
+        );
+        assert_eq!(
+            typed_string(DataType::Interval, "1 day").to_string(),
+            "INTERVAL '1 day'"
+        );
+    }
+
+    #[test]
+    fn lock_clause_test() {
+        let statement = select_table("jobs".to_string())
+            .selection(col("queue").eq(param("mail")))
+            .order_by(Some("id".to_string()))
+            .limit(Some(1))
+            .for_update()
+            .skip_locked()
+            .build();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT * FROM jobs WHERE queue = $1 ORDER BY id LIMIT 1 FOR UPDATE SKIP LOCKED"
+        );
+
+        let query = QueryBuilder::new()
+            .select()
+            .from_as("jobs".to_string(), "j".to_string())
+            .join(table("workers"), col("workers.id").eq(col("j.worker_id")))
+            .offset(Some(10))
+            .for_share()
+            .of("j")
+            .nowait()
+            .lock(LockType::KeyShare)
+            .of("workers")
+            .build();
+        assert_eq!(
+            query.to_string(),
+            "SELECT * FROM jobs AS j JOIN workers ON workers.id = j.worker_id OFFSET 10 \
+             FOR SHARE OF j NOWAIT FOR KEY SHARE OF workers"
+        );
+    }
+
+    #[test]
+    fn alter_table_test() {
+        let alter = alter_table("orders".to_string())
+            .add_column("note".to_string(), DataType::Text, vec![])
+            .add_column("paid".to_string(), DataType::Boolean, not_null())
+            .drop_column("legacy".to_string())
+            .set_data_type("price".to_string(), DataType::Decimal(Some(10), Some(2)))
+            .alter_column(
+                "user_id".to_string(),
+                AlterColumnOperation::SetDataType {
+                    data_type: DataType::BigInt(None),
+                    using: Some(col("user_id").double_colon(DataType::BigInt(None))),
+                },
+            )
+            .set_default("paid".to_string(), Expr::from(false))
+            .drop_default("note".to_string())
+            .set_not_null("price".to_string())
+            .drop_not_null("note".to_string())
+            .build();
+        assert_eq!(
+            alter.to_string(),
+            "ALTER TABLE orders ADD COLUMN note TEXT, ADD COLUMN paid BOOLEAN NOT NULL, \
+             DROP COLUMN legacy, ALTER COLUMN price SET DATA TYPE NUMERIC(10,2), \
+             ALTER COLUMN user_id SET DATA TYPE BIGINT USING user_id::BIGINT, \
+             ALTER COLUMN paid SET DEFAULT FALSE, ALTER COLUMN note DROP DEFAULT, \
+             ALTER COLUMN price SET NOT NULL, ALTER COLUMN note DROP NOT NULL"
+        );
+
+        let alter = alter_table("orders".to_string())
+            .drop_constraint_with("fk_legacy".to_string(), true, false)
+            .drop_column_with("legacy".to_string(), true, true)
+            .build();
+        assert_eq!(
+            alter.to_string(),
+            "ALTER TABLE orders DROP CONSTRAINT IF EXISTS fk_legacy, \
+             DROP COLUMN IF EXISTS legacy CASCADE"
+        );
+
+        let statements = vec![
+            Statement::AlterTableStmt(
+                alter_table("orders".to_string())
+                    .rename_column("price".to_string(), "amount".to_string())
+                    .build(),
+            ),
+            Statement::AlterTableStmt(
+                alter_table("orders".to_string())
+                    .drop_constraint("fk_user".to_string())
+                    .add_foreign_key_constraint(
+                        "fk_user".to_string(),
+                        "user_id".to_string(),
+                        "users".to_string(),
+                        "id".to_string(),
+                    )
+                    .build(),
+            ),
+        ];
+        assert_eq!(
+            Statement::to_sql_batch(&statements),
+            "ALTER TABLE orders RENAME COLUMN price TO amount;\n\
+             ALTER TABLE orders DROP CONSTRAINT fk_user, \
+             ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id);"
+        );
+    }
+
+    #[test]
+    fn drop_truncate_test() {
+        assert_eq!(
+            drop_table("orders".to_string()).build().to_string(),
+            "DROP TABLE orders"
+        );
+        assert_eq!(
+            drop_table("orders".to_string())
+                .name_in("billing".to_string(), "invoices".to_string())
+                .if_exists()
+                .cascade()
+                .build()
+                .to_string(),
+            "DROP TABLE IF EXISTS orders, billing.invoices CASCADE"
+        );
+        assert_eq!(
+            drop_index("orders_user_id_idx".to_string())
+                .restrict()
+                .build()
+                .to_string(),
+            "DROP INDEX orders_user_id_idx RESTRICT"
+        );
+        assert_eq!(
+            DropBuilder::new(ObjectType::Type)
+                .name("order_status".to_string())
+                .if_exists()
+                .build()
+                .to_string(),
+            "DROP TYPE IF EXISTS order_status"
+        );
+
+        let statements = vec![
+            Statement::TruncateStmt(
+                truncate("orders".to_string())
+                    .table("user".to_string())
+                    .restart_identity()
+                    .cascade()
+                    .build(),
+            ),
+            Statement::DropStmt(
+                drop_table_in("billing".to_string(), "invoices".to_string()).build(),
+            ),
+        ];
+        assert_eq!(
+            Statement::to_sql_batch(&statements),
+            "TRUNCATE orders, \"user\" RESTART IDENTITY CASCADE;\nDROP TABLE billing.invoices;"
+        );
+    }
+
+    #[test]
+    fn create_index_test() {
+        let index = create_index("orders_user_id_idx".to_string(), "orders".to_string())
+            .column("user_id".to_string())
+            .build();
+        assert_eq!(
+            index.to_string(),
+            "CREATE INDEX orders_user_id_idx ON orders (user_id)"
+        );
+
+        let index = create_index("users_email_key".to_string(), "users".to_string())
+            .unique()
+            .concurrently()
+            .if_not_exists()
+            .expression(function("lower", vec![col("email")]).build())
+            .include(vec!["id".to_string()])
+            .selection(col("deleted_at").is_null())
+            .build();
+        assert_eq!(
+            index.to_string(),
+            "CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS users_email_key ON users \
+             (lower(email)) INCLUDE (id) WHERE deleted_at IS NULL"
+        );
+        // DDL takes no bind parameters, only literals
+        assert!(std::panic::catch_unwind(|| create_index(
+            "users_active_idx".to_string(),
+            "users".to_string()
+        )
+        .column("id".to_string())
+        .selection(col("active").eq(param(true)))
+        .build())
+        .is_err());
+
+        let index = create_index_in(
+            "invoices_recent_idx".to_string(),
+            "billing".to_string(),
+            "invoices".to_string(),
+        )
+        .using(IndexMethod::Brin)
+        .columns(vec!["user_id".to_string(), "issued_at".to_string()])
+        .ordered(col("amount").gt_eq(100).desc().nulls_last())
+        .build();
+        assert_eq!(
+            index.to_string(),
+            "CREATE INDEX invoices_recent_idx ON billing.invoices USING brin \
+             (user_id, issued_at, (amount >= 100) DESC NULLS LAST)"
+        );
+    }
+
     fn find_orders_in_list() -> QueryStmt {
         QueryStmt {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
                 projection: vec![SelectItem::Wildcard],
                 from: vec![TableWithJoins {
                     relation: TableFactor::Table {
-                        name: ObjectName(vec![Ident {
-                            value: "orders".to_string(),
-                        }]),
+                        name: ObjectName(vec![Ident::new("orders".to_string())]),
+                        alias: None,
                     },
+                    joins: vec![],
                 }],
                 selection: Some(Expr::InList {
-                    expr: Box::new(Expr::Identifier(Ident {
-                        value: "user_id".to_string(),
-                    })),
+                    expr: Box::new(Expr::Identifier(Ident::new("user_id".to_string()))),
                     list: vec![
                         Expr::Value(Value::Number("1".to_string(), false)),
                         Expr::Value(Value::Number("2".to_string(), false)),
@@ -222,33 +1377,46 @@ mod tests {
                     ],
                     negated: false,
                 }),
-                group_by: None,
+                group_by: vec![],
                 sort_by: vec![],
                 having: None,
+                named_window: vec![],
             })),
-            order_by: None,
+            order_by: vec![],
             limit: None,
+            offset: None,
+            fetch: None,
+            locks: vec![],
         }
     }
 
     fn find_first_user() -> QueryStmt {
         QueryStmt {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
                 projection: vec![SelectItem::Wildcard],
                 from: vec![TableWithJoins {
                     relation: TableFactor::Table {
-                        name: ObjectName(vec![Ident {
-                            value: "users".to_string(),
-                        }]),
+                        name: ObjectName(vec![Ident::new("users".to_string())]),
+                        alias: None,
                     },
+                    joins: vec![],
                 }],
                 selection: None,
-                group_by: None,
+                group_by: vec![],
                 sort_by: vec![],
                 having: None,
+                named_window: vec![],
             })),
-            order_by: Some("id".to_string()),
+            order_by: vec![OrderByExpr {
+                expr: Expr::Identifier(Ident::new("id".to_string())),
+                asc: None,
+                nulls_first: None,
+            }],
             limit: Some(1),
+            offset: None,
+            fetch: None,
+            locks: vec![],
         }
     }
 
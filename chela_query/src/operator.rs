@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+/// Precedence of `IS [ NOT ] NULL`
+pub(crate) const IS_PRECEDENCE: u8 = 17;
+/// Precedence of `IN`, `BETWEEN`, `LIKE` and `ILIKE`
+pub(crate) const PATTERN_PRECEDENCE: u8 = 25;
+/// Precedence of the Postgres `::` cast, tighter than any operator
+pub(crate) const DOUBLE_COLON_PRECEDENCE: u8 = 60;
+
+/// Unary operators
+#[derive(Debug, PartialEq, Clone)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    Not,
+}
+
+impl UnaryOperator {
+    /// Binding strength of the operator, higher binds tighter
+    pub fn precedence(&self) -> u8 {
+        match self {
+            UnaryOperator::Not => 15,
+            UnaryOperator::Plus | UnaryOperator::Minus => 50,
+        }
+    }
+}
+
+impl Display for UnaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Not => "NOT",
+        })
+    }
+}
+
+/// Binary operators
+#[derive(Debug, PartialEq, Clone)]
+pub enum BinaryOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+    Gt,
+    Lt,
+    GtEq,
+    LtEq,
+    Eq,
+    NotEq,
+    And,
+    Or,
+}
+
+impl BinaryOperator {
+    /// Binding strength of the operator, higher binds tighter
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 5,
+            BinaryOperator::And => 10,
+            BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Gt
+            | BinaryOperator::Lt
+            | BinaryOperator::GtEq
+            | BinaryOperator::LtEq => 20,
+            BinaryOperator::Plus | BinaryOperator::Minus => 30,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 40,
+        }
+    }
+}
+
+impl Display for BinaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::Gt => ">",
+            BinaryOperator::Lt => "<",
+            BinaryOperator::GtEq => ">=",
+            BinaryOperator::LtEq => "<=",
+            BinaryOperator::Eq => "=",
+            BinaryOperator::NotEq => "<>",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+        })
+    }
+}
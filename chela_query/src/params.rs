@@ -0,0 +1,486 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use postgres_types::ToSql;
+
+use crate::{
+    alter::{AlterColumnOperation, AlterTableOperation, AlterTableStmt},
+    create::{ColumnDef, ColumnOption, CreateStmt},
+    delete::DeleteStmt,
+    index::CreateIndexStmt,
+    insert::{DoUpdate, InsertStmt, OnConflict, OnConflictAction, OnInsert},
+    query::{
+        Cte, Expr, Fetch, Function, FunctionArg, Join, JoinConstraint, NamedWindowDefinition,
+        Offset, OrderByExpr, QueryStmt, Returning, Select, SelectItem, SetExpr, TableFactor,
+        TableWithJoins, WindowFrame, WindowFrameBound, WindowSpec, WindowType, With,
+    },
+    statement::Statement,
+    update::{Assignment, UpdateStmt},
+    values::{Value, Values},
+};
+
+/// A value bound to a `$n` placeholder instead of being inlined in the SQL text
+pub trait Param: ToSql + Sync + Send {
+    fn clone_param(&self) -> Box<dyn Param>;
+}
+
+impl<T> Param for T
+where
+    T: ToSql + Sync + Send + Clone + 'static,
+{
+    fn clone_param(&self) -> Box<dyn Param> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Param> {
+    fn clone(&self) -> Self {
+        self.clone_param()
+    }
+}
+
+/// A bound value inside a statement. It travels with the expression it was
+/// bound in, so subqueries keep their values when nested in another statement,
+/// and gets its `$n` number when the whole statement is built.
+pub struct BoundParam {
+    index: AtomicUsize,
+    value: Box<dyn Param>,
+}
+
+impl BoundParam {
+    pub fn new(value: Box<dyn Param>) -> Self {
+        BoundParam {
+            index: AtomicUsize::new(0),
+            value,
+        }
+    }
+
+    /// The `n` of `$n`, 0 until the statement holding the value is built
+    pub fn index(&self) -> usize {
+        self.index.load(Ordering::Relaxed)
+    }
+
+    pub fn value(&self) -> &(dyn ToSql + Sync) {
+        self.value.as_ref()
+    }
+
+    fn number(&self, index: usize) {
+        self.index.store(index, Ordering::Relaxed);
+    }
+}
+
+impl Clone for BoundParam {
+    fn clone(&self) -> Self {
+        BoundParam {
+            index: AtomicUsize::new(self.index()),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BoundParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${} = {:?}", self.index(), self.value)
+    }
+}
+
+/// Bound values are equal when they take the same placeholder, the values
+/// themselves have no common type to compare them as
+impl PartialEq for BoundParam {
+    fn eq(&self, other: &Self) -> bool {
+        self.index() == other.index()
+    }
+}
+
+/// Finds the values bound in a statement, so they can be numbered and sent
+/// along with its SQL text
+pub trait CollectParams {
+    /// Pushes the values bound in `self` onto `params`, in rendering order
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>);
+
+    /// Numbers the placeholders `$1`, `$2`, ... in rendering order, the builders
+    /// do this when they build a statement
+    fn number_params(&self) -> Vec<&BoundParam> {
+        let mut params = Vec::new();
+        self.collect_params(&mut params);
+        for (i, param) in params.iter().enumerate() {
+            param.number(i + 1);
+        }
+        params
+    }
+
+    /// The bound values in placeholder order, in the shape
+    /// `tokio_postgres::Client::query` takes them
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.number_params()
+            .into_iter()
+            .map(BoundParam::value)
+            .collect()
+    }
+}
+
+impl<T: CollectParams> CollectParams for Option<T> {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        if let Some(value) = self {
+            value.collect_params(params);
+        }
+    }
+}
+
+impl<T: CollectParams> CollectParams for Vec<T> {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        for value in self {
+            value.collect_params(params);
+        }
+    }
+}
+
+impl<T: CollectParams> CollectParams for Box<T> {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.as_ref().collect_params(params);
+    }
+}
+
+impl CollectParams for Statement {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            Statement::QueryStmt(query_stmt) => query_stmt.collect_params(params),
+            Statement::InsertStmt(insert_stmt) => insert_stmt.collect_params(params),
+            Statement::UpdateStmt(update_stmt) => update_stmt.collect_params(params),
+            Statement::DeleteStmt(delete_stmt) => delete_stmt.collect_params(params),
+            Statement::CreateStmt(create_stmt) => create_stmt.collect_params(params),
+            Statement::AlterTableStmt(alter_stmt) => alter_stmt.collect_params(params),
+            Statement::CreateIndexStmt(index_stmt) => index_stmt.collect_params(params),
+            Statement::DropStmt(_)
+            | Statement::TruncateStmt(_)
+            | Statement::CreateSchemaStmt(_) => (),
+        }
+    }
+}
+
+impl CollectParams for QueryStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.with.collect_params(params);
+        self.body.collect_params(params);
+        self.order_by.collect_params(params);
+        self.offset.collect_params(params);
+        self.fetch.collect_params(params);
+    }
+}
+
+impl CollectParams for With {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.ctes.collect_params(params);
+    }
+}
+
+impl CollectParams for Cte {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.query.collect_params(params);
+    }
+}
+
+impl CollectParams for SetExpr {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            SetExpr::Select(select) => select.collect_params(params),
+            SetExpr::Query(query) => query.collect_params(params),
+            SetExpr::SetOperation { left, right, .. } => {
+                left.collect_params(params);
+                right.collect_params(params);
+            }
+            SetExpr::Values(values) => values.collect_params(params),
+        }
+    }
+}
+
+impl CollectParams for Values {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.0.collect_params(params);
+    }
+}
+
+impl CollectParams for Select {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.projection.collect_params(params);
+        self.from.collect_params(params);
+        self.selection.collect_params(params);
+        self.group_by.collect_params(params);
+        self.having.collect_params(params);
+        self.named_window.collect_params(params);
+    }
+}
+
+impl CollectParams for SelectItem {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                expr.collect_params(params)
+            }
+            SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => (),
+        }
+    }
+}
+
+impl CollectParams for TableWithJoins {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.relation.collect_params(params);
+        self.joins.collect_params(params);
+    }
+}
+
+impl CollectParams for Join {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.relation.collect_params(params);
+        if let Some(JoinConstraint::On(on)) = self.join_operator.constraint() {
+            on.collect_params(params);
+        }
+    }
+}
+
+impl CollectParams for TableFactor {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            TableFactor::Table { .. } => (),
+            TableFactor::Derived { subquery, .. } => subquery.collect_params(params),
+        }
+    }
+}
+
+impl CollectParams for OrderByExpr {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.expr.collect_params(params);
+    }
+}
+
+impl CollectParams for Offset {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.value.collect_params(params);
+    }
+}
+
+impl CollectParams for Fetch {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.quantity.collect_params(params);
+    }
+}
+
+impl CollectParams for NamedWindowDefinition {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.1.collect_params(params);
+    }
+}
+
+impl CollectParams for WindowSpec {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.partition_by.collect_params(params);
+        self.order_by.collect_params(params);
+        self.window_frame.collect_params(params);
+    }
+}
+
+impl CollectParams for WindowFrame {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.start_bound.collect_params(params);
+        self.end_bound.collect_params(params);
+    }
+}
+
+impl CollectParams for WindowFrameBound {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            WindowFrameBound::CurrentRow => (),
+            WindowFrameBound::Preceding(n) | WindowFrameBound::Following(n) => {
+                n.collect_params(params)
+            }
+        }
+    }
+}
+
+impl CollectParams for Function {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.args.collect_params(params);
+        self.filter.collect_params(params);
+        if let Some(WindowType::WindowSpec(spec)) = &self.over {
+            spec.collect_params(params);
+        }
+    }
+}
+
+impl CollectParams for FunctionArg {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            FunctionArg::Unnamed(expr) => expr.collect_params(params),
+            FunctionArg::Wildcard => (),
+        }
+    }
+}
+
+impl CollectParams for Expr {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            Expr::Value(Value::Param(param)) => params.push(param),
+            Expr::Value(_)
+            | Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::TypedString { .. } => (),
+            Expr::InList { expr, list, .. } => {
+                expr.collect_params(params);
+                list.collect_params(params);
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                left.collect_params(params);
+                right.collect_params(params);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::Nested(expr)
+            | Expr::Cast { expr, .. } => expr.collect_params(params),
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                expr.collect_params(params);
+                low.collect_params(params);
+                high.collect_params(params);
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                expr.collect_params(params);
+                pattern.collect_params(params);
+            }
+            Expr::Function(function) => function.collect_params(params),
+            Expr::Subquery(subquery) | Expr::Exists { subquery, .. } => {
+                subquery.collect_params(params)
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                expr.collect_params(params);
+                subquery.collect_params(params);
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                operand.collect_params(params);
+                for (condition, result) in conditions.iter().zip(results) {
+                    condition.collect_params(params);
+                    result.collect_params(params);
+                }
+                else_result.collect_params(params);
+            }
+        }
+    }
+}
+
+impl CollectParams for InsertStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.source.collect_params(params);
+        self.on.collect_params(params);
+        self.returning.collect_params(params);
+    }
+}
+
+impl CollectParams for OnInsert {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            OnInsert::OnConflict(on_conflict) => on_conflict.collect_params(params),
+        }
+    }
+}
+
+impl CollectParams for OnConflict {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match &self.action {
+            OnConflictAction::DoNothing => (),
+            OnConflictAction::DoUpdate(do_update) => do_update.collect_params(params),
+        }
+    }
+}
+
+impl CollectParams for DoUpdate {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.assignments.collect_params(params);
+        self.selection.collect_params(params);
+    }
+}
+
+impl CollectParams for UpdateStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.assignments.collect_params(params);
+        self.selection.collect_params(params);
+        self.returning.collect_params(params);
+    }
+}
+
+impl CollectParams for Assignment {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.value.collect_params(params);
+    }
+}
+
+impl CollectParams for Returning {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.0.collect_params(params);
+    }
+}
+
+impl CollectParams for DeleteStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.selection.collect_params(params);
+        self.returning.collect_params(params);
+    }
+}
+
+impl CollectParams for CreateStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.columns.collect_params(params);
+    }
+}
+
+impl CollectParams for ColumnDef {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        for option_def in &self.options {
+            match &option_def.option {
+                ColumnOption::Default(expr) | ColumnOption::Check(expr) => {
+                    expr.collect_params(params)
+                }
+                ColumnOption::Null
+                | ColumnOption::NotNull
+                | ColumnOption::Unique { .. }
+                | ColumnOption::ForeignKey { .. } => (),
+            }
+        }
+    }
+}
+
+impl CollectParams for AlterTableStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.operations.collect_params(params);
+    }
+}
+
+impl CollectParams for AlterTableOperation {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        match self {
+            AlterTableOperation::AddColumn { column_def } => column_def.collect_params(params),
+            AlterTableOperation::AlterColumn { op, .. } => match op {
+                AlterColumnOperation::SetDefault { value } => value.collect_params(params),
+                AlterColumnOperation::SetDataType { using, .. } => using.collect_params(params),
+                AlterColumnOperation::SetNotNull
+                | AlterColumnOperation::DropNotNull
+                | AlterColumnOperation::DropDefault => (),
+            },
+            AlterTableOperation::DropColumn { .. }
+            | AlterTableOperation::RenameColumn { .. }
+            | AlterTableOperation::AddConstraint(_)
+            | AlterTableOperation::DropConstraint { .. } => (),
+        }
+    }
+}
+
+impl CollectParams for CreateIndexStmt {
+    fn collect_params<'a>(&'a self, params: &mut Vec<&'a BoundParam>) {
+        self.columns.collect_params(params);
+        self.predicate.collect_params(params);
+    }
+}
@@ -1,40 +1,362 @@
 use std::fmt::Display;
 
-use crate::{display::display_comma_separated, values::Value, values::Values};
+use crate::{
+    create::DataType,
+    display::{display_comma_separated, display_separated},
+    keywords::is_reserved,
+    operator::{
+        BinaryOperator, UnaryOperator, DOUBLE_COLON_PRECEDENCE, IS_PRECEDENCE, PATTERN_PRECEDENCE,
+    },
+    values::Value,
+    values::Values,
+};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum SelectItem {
+    /// Any expression, not followed by `[ AS ] alias`
+    UnnamedExpr(Expr),
+    /// An expression, followed by `AS alias`
+    ExprWithAlias { expr: Expr, alias: Ident },
+    /// `alias.*` or even `schema.table.*`
+    QualifiedWildcard(ObjectName),
+    /// An unqualified `*`
     Wildcard,
 }
 
-#[derive(Debug, PartialEq)]
+/// `RETURNING` projection of INSERT, UPDATE and DELETE
+#[derive(Debug, PartialEq, Clone)]
+pub struct Returning(pub Vec<SelectItem>);
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct QueryStmt {
+    /// WITH (common table expressions, or CTEs)
+    pub with: Option<With>,
     // SELECT or UNION / EXCEPT / INTERSECT
     pub body: SetExpr,
     // ORDER BY
-    pub order_by: Option<String>, //Vec<OrderByExpr>,
+    pub order_by: Vec<OrderByExpr>,
     /// `LIMIT { <N> | ALL }`
     pub limit: Option<i64>, //Option<Expr>,
-                                  // `OFFSET <N> [ { ROW | ROWS } ]`
-                                  // pub offset: Option<Offset>
+    /// `OFFSET <N> [ { ROW | ROWS } ]`
+    pub offset: Option<Offset>,
+    /// `FETCH { FIRST | NEXT } <N> { ROW | ROWS } { ONLY | WITH TIES }`
+    pub fetch: Option<Fetch>,
+    /// `FOR { UPDATE | SHARE } [ OF table ] [ NOWAIT | SKIP LOCKED ]`
+    pub locks: Vec<LockClause>,
+}
+
+/// `WITH [ RECURSIVE ] name AS (query), ...` in front of a query
+#[derive(Debug, PartialEq, Clone)]
+pub struct With {
+    /// Lets a CTE refer to its own name, e.g. to walk a tree
+    pub recursive: bool,
+    pub ctes: Vec<Cte>,
+}
+
+impl Display for With {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "WITH {}{}",
+            if self.recursive { "RECURSIVE " } else { "" },
+            display_comma_separated(&self.ctes)
+        )
+    }
+}
+
+/// A single common table expression, `name [(columns)] AS (query)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cte {
+    pub alias: TableAlias,
+    pub query: QueryStmt,
+}
+
+impl Display for Cte {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} AS ({})", self.alias, self.query)
+    }
+}
+
+/// An `ORDER BY` expression
+#[derive(Debug, PartialEq, Clone)]
+pub struct OrderByExpr {
+    pub expr: Expr,
+    /// Optional `ASC` or `DESC`
+    pub asc: Option<bool>,
+    /// Optional `NULLS FIRST` or `NULLS LAST`
+    pub nulls_first: Option<bool>,
+}
+
+impl Display for OrderByExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expr)?;
+        match self.asc {
+            Some(true) => write!(f, " ASC")?,
+            Some(false) => write!(f, " DESC")?,
+            None => (),
+        }
+        match self.nulls_first {
+            Some(true) => write!(f, " NULLS FIRST")?,
+            Some(false) => write!(f, " NULLS LAST")?,
+            None => (),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Offset {
+    pub value: Expr,
+    pub rows: OffsetRows,
+}
+
+impl Display for Offset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OFFSET {}{}", self.value, self.rows)
+    }
+}
+
+/// Stores the keyword after `OFFSET <number>`
+#[derive(Debug, PartialEq, Clone)]
+pub enum OffsetRows {
+    /// Plain `OFFSET <N>`
+    None,
+    Row,
+    Rows,
+}
+
+impl Display for OffsetRows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OffsetRows::None => Ok(()),
+            OffsetRows::Row => write!(f, " ROW"),
+            OffsetRows::Rows => write!(f, " ROWS"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Fetch {
+    pub with_ties: bool,
+    pub quantity: Option<Expr>,
 }
 
-#[derive(Debug, PartialEq)]
+impl Display for Fetch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let extension = if self.with_ties { "WITH TIES" } else { "ONLY" };
+        if let Some(ref quantity) = self.quantity {
+            write!(f, "FETCH FIRST {} ROWS {}", quantity, extension)
+        } else {
+            write!(f, "FETCH FIRST ROWS {}", extension)
+        }
+    }
+}
+
+/// Row-level lock taken on the rows a SELECT returns
+#[derive(Debug, PartialEq, Clone)]
+pub struct LockClause {
+    pub lock_type: LockType,
+    /// `OF table, ...`, only rows of these tables are locked. Postgres takes
+    /// the unqualified table name or alias as it appears in FROM.
+    pub of: Vec<Ident>,
+    /// What to do with rows another transaction already locked, wait when None
+    pub nonblock: Option<NonBlock>,
+}
+
+impl Display for LockClause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FOR {}", self.lock_type)?;
+        if !self.of.is_empty() {
+            write!(f, " OF {}", display_comma_separated(&self.of))?;
+        }
+        if let Some(nonblock) = &self.nonblock {
+            write!(f, " {}", nonblock)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LockType {
+    Update,
+    NoKeyUpdate,
+    Share,
+    KeyShare,
+}
+
+impl Display for LockType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LockType::Update => "UPDATE",
+            LockType::NoKeyUpdate => "NO KEY UPDATE",
+            LockType::Share => "SHARE",
+            LockType::KeyShare => "KEY SHARE",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NonBlock {
+    /// Fail at once instead of waiting for the lock
+    Nowait,
+    /// Leave out the rows that cannot be locked at once, as job queues do
+    SkipLocked,
+}
+
+impl Display for NonBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            NonBlock::Nowait => "NOWAIT",
+            NonBlock::SkipLocked => "SKIP LOCKED",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum SetExpr {
     /// Restricted SELECT .. FROM .. HAVING (no ORDER BY or set operations)
     Select(Box<Select>),
+    /// Parenthesized query, for operands carrying their own ORDER BY or LIMIT
+    Query(Box<QueryStmt>),
+    /// UNION/EXCEPT/INTERSECT of two queries
+    SetOperation {
+        op: SetOperator,
+        all: bool,
+        left: Box<SetExpr>,
+        right: Box<SetExpr>,
+    },
     Values(Values),
 }
 
+impl SetExpr {
+    /// Binding strength of a set operation, INTERSECT binds tighter than
+    /// UNION and EXCEPT. Higher binds tighter.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            SetExpr::SetOperation { op, .. } => op.precedence(),
+            SetExpr::Select(_) | SetExpr::Query(_) | SetExpr::Values(_) => u8::MAX,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SetOperator {
+    Union,
+    Except,
+    Intersect,
+}
+
+impl SetOperator {
+    pub fn precedence(&self) -> u8 {
+        match self {
+            SetOperator::Union | SetOperator::Except => 1,
+            SetOperator::Intersect => 2,
+        }
+    }
+}
+
+impl Display for SetOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SetOperator::Union => "UNION",
+            SetOperator::Except => "EXCEPT",
+            SetOperator::Intersect => "INTERSECT",
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct TableWithJoins {
     pub relation: TableFactor,
-    // pub joins: Vec<Join>,
+    pub joins: Vec<Join>,
 }
 
 impl Display for TableWithJoins {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.relation).unwrap();
+        write!(f, "{}", self.relation)?;
+        for join in &self.joins {
+            write!(f, "{}", join)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Join {
+    pub relation: TableFactor,
+    pub join_operator: JoinOperator,
+}
+
+impl Display for Join {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn suffix(constraint: &JoinConstraint) -> String {
+            match constraint {
+                JoinConstraint::On(expr) => format!(" ON {}", expr),
+                JoinConstraint::Using(attrs) => {
+                    format!(" USING ({})", display_comma_separated(attrs))
+                }
+            }
+        }
+        match &self.join_operator {
+            JoinOperator::Inner(constraint) => {
+                write!(f, " JOIN {}{}", self.relation, suffix(constraint))
+            }
+            JoinOperator::LeftOuter(constraint) => {
+                write!(f, " LEFT JOIN {}{}", self.relation, suffix(constraint))
+            }
+            JoinOperator::RightOuter(constraint) => {
+                write!(f, " RIGHT JOIN {}{}", self.relation, suffix(constraint))
+            }
+            JoinOperator::FullOuter(constraint) => {
+                write!(f, " FULL JOIN {}{}", self.relation, suffix(constraint))
+            }
+            JoinOperator::CrossJoin => write!(f, " CROSS JOIN {}", self.relation),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JoinOperator {
+    Inner(JoinConstraint),
+    LeftOuter(JoinConstraint),
+    RightOuter(JoinConstraint),
+    FullOuter(JoinConstraint),
+    CrossJoin,
+}
+
+impl JoinOperator {
+    /// The ON/USING constraint, CROSS JOIN has none
+    pub fn constraint(&self) -> Option<&JoinConstraint> {
+        match self {
+            JoinOperator::Inner(constraint)
+            | JoinOperator::LeftOuter(constraint)
+            | JoinOperator::RightOuter(constraint)
+            | JoinOperator::FullOuter(constraint) => Some(constraint),
+            JoinOperator::CrossJoin => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JoinConstraint {
+    /// `ON <expr>`
+    On(Expr),
+    /// `USING (<columns>)`
+    Using(Vec<Ident>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TableAlias {
+    pub name: Ident,
+    pub columns: Vec<Ident>,
+}
+
+impl Display for TableAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.columns.is_empty() {
+            write!(f, " ({})", display_comma_separated(&self.columns))?;
+        }
         Ok(())
     }
 }
@@ -43,7 +365,7 @@ impl Display for TableWithJoins {
 pub enum TableFactor {
     Table {
         name: ObjectName,
-        // alias: Option<TableAlias>,
+        alias: Option<TableAlias>,
         // Arguments of a table-valued function, as supported by Postgres
         // and MSSQL. Note that deprecated MSSQL `FROM foo (NOLOCK)` syntax
         // will also be parsed as `args`.
@@ -51,12 +373,30 @@ pub enum TableFactor {
         // MSSQL-specific `WITH (...)` hints such as NOLOCK.
         // with_hints: Vec<Expr>,
     },
+    /// `(SELECT ...) AS alias`, Postgres requires the alias before version 16
+    Derived {
+        subquery: Box<QueryStmt>,
+        alias: Option<TableAlias>,
+    },
 }
 
 impl Display for TableFactor {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            TableFactor::Table { name, .. } => write!(f, "{}", name),
+            TableFactor::Table { name, alias } => {
+                write!(f, "{}", name)?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {}", alias)?;
+                }
+                Ok(())
+            }
+            TableFactor::Derived { subquery, alias } => {
+                write!(f, "({})", subquery)?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {}", alias)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -64,9 +404,16 @@ impl Display for TableFactor {
 #[derive(Debug, PartialEq, Clone)]
 pub struct ObjectName(pub Vec<Ident>);
 
+impl ObjectName {
+    /// `schema.name`
+    pub fn qualified(schema: String, name: String) -> Self {
+        ObjectName(vec![Ident::new(schema), Ident::new(name)])
+    }
+}
+
 impl Display for ObjectName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", display_comma_separated(&self.0))
+        write!(f, "{}", display_separated(&self.0, "."))
     }
 }
 
@@ -74,19 +421,64 @@ impl Display for ObjectName {
 pub struct Ident {
     /// The value of the identifier without quotes.
     pub value: String,
-    // The starting quote if any. Valid quote characters are the single quote,
-    // double quote, backtick, and opening square bracket.
-    // pub quote_style: Option<char>,
+    /// The quote if any, Postgres only quotes identifiers with `"`. Without one,
+    /// the identifier is double quoted only when Postgres would not read it back as is.
+    pub quote_style: Option<char>,
+}
+
+impl Ident {
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        Ident {
+            value: value.into(),
+            quote_style: None,
+        }
+    }
+
+    /// Always quoted, e.g. to keep the case of a mixed-case name. Panics on any
+    /// quote but `"`, the only one Postgres reads as an identifier quote.
+    pub fn with_quote<S: Into<String>>(quote: char, value: S) -> Self {
+        assert!(
+            quote == '"',
+            "Postgres quotes identifiers with '\"', not {:?}",
+            quote
+        );
+        Ident {
+            value: value.into(),
+            quote_style: Some(quote),
+        }
+    }
+
+    /// Whether the value survives unquoted: lower case, not a reserved word
+    pub fn is_bare(&self) -> bool {
+        let mut chars = self.value.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_lowercase() || c == '_' => (),
+            _ => return false,
+        }
+        chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$')
+            && !is_reserved(&self.value)
+    }
 }
 
 impl Display for Ident {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.value).unwrap();
-        Ok(())
+        let quote = match self.quote_style {
+            Some(quote) => quote,
+            None if self.is_bare() => return write!(f, "{}", self.value),
+            None => '"',
+        };
+        write!(f, "{}", quote)?;
+        for c in self.value.chars() {
+            if c == quote {
+                write!(f, "{}", quote)?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, "{}", quote)
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Select {
     // pub distinct: bool,
     /// projection expressions
@@ -96,41 +488,241 @@ pub struct Select {
     /// WHERE
     pub selection: Option<Expr>,
     /// GROUP BY
-    pub group_by: Option<String>, //Vec<Expr>,
+    pub group_by: Vec<Expr>,
     /// SORT BY (Hive)
     pub sort_by: Vec<Expr>,
     /// HAVING
     pub having: Option<Expr>,
+    /// WINDOW
+    pub named_window: Vec<NamedWindowDefinition>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expr {
     Value(Value),
     Identifier(Ident),
+    /// Multi-part identifier, e.g. `table_alias.column`
+    CompoundIdentifier(Vec<Ident>),
+    /// `[ NOT ] IN (val1, val2, ...)`
     InList {
         expr: Box<Expr>,
         list: Vec<Expr>,
         negated: bool,
     },
+    /// Binary operation e.g. `1 + 1` or `foo > bar`
+    BinaryOp {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// Unary operation e.g. `NOT foo`
+    UnaryOp {
+        op: UnaryOperator,
+        expr: Box<Expr>,
+    },
+    /// `IS NULL` operator
+    IsNull(Box<Expr>),
+    /// `IS NOT NULL` operator
+    IsNotNull(Box<Expr>),
+    /// `<expr> [ NOT ] BETWEEN <low> AND <high>`
+    Between {
+        expr: Box<Expr>,
+        negated: bool,
+        low: Box<Expr>,
+        high: Box<Expr>,
+    },
+    /// `<expr> [ NOT ] LIKE <pattern>`
+    Like {
+        negated: bool,
+        expr: Box<Expr>,
+        pattern: Box<Expr>,
+    },
+    /// `<expr> [ NOT ] ILIKE <pattern>`, the case-insensitive LIKE of Postgres
+    ILike {
+        negated: bool,
+        expr: Box<Expr>,
+        pattern: Box<Expr>,
+    },
+    /// Nested expression e.g. `(foo > bar)` or `(1)`
+    Nested(Box<Expr>),
+    /// Function call e.g. `count(*)` or `sum(DISTINCT price)`
+    Function(Function),
+    /// A parenthesized subquery `(SELECT ...)`, returning a single value
+    Subquery(Box<QueryStmt>),
+    /// `[ NOT ] EXISTS (SELECT ...)`
+    Exists {
+        subquery: Box<QueryStmt>,
+        negated: bool,
+    },
+    /// `<expr> [ NOT ] IN (SELECT ...)`
+    InSubquery {
+        expr: Box<Expr>,
+        subquery: Box<QueryStmt>,
+        negated: bool,
+    },
+    /// `CAST(<expr> AS <type>)` or `<expr>::<type>`
+    Cast {
+        kind: CastKind,
+        expr: Box<Expr>,
+        data_type: DataType,
+    },
+    /// A literal of the given type, e.g. `DATE '2020-01-01'` or `INTERVAL '1 day'`
+    TypedString {
+        data_type: DataType,
+        value: String,
+    },
+    /// `CASE [<operand>] WHEN <condition> THEN <result> ... [ELSE <result>] END`,
+    /// `conditions` and `results` are paired up by position
+    Case {
+        operand: Option<Box<Expr>>,
+        conditions: Vec<Expr>,
+        results: Vec<Expr>,
+        else_result: Option<Box<Expr>>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CastKind {
+    /// The standard `CAST(<expr> AS <type>)`
+    Cast,
+    /// The Postgres shorthand `<expr>::<type>`
+    DoubleColon,
+}
+
+impl Expr {
+    /// Binding strength of the expression, following the Postgres operator
+    /// precedence table. Higher binds tighter.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Expr::BinaryOp { op, .. } => op.precedence(),
+            Expr::UnaryOp { op, .. } => op.precedence(),
+            Expr::IsNull(_) | Expr::IsNotNull(_) => IS_PRECEDENCE,
+            Expr::InList { .. }
+            | Expr::InSubquery { .. }
+            | Expr::Between { .. }
+            | Expr::Like { .. }
+            | Expr::ILike { .. } => PATTERN_PRECEDENCE,
+            Expr::Exists { negated: true, .. } => UnaryOperator::Not.precedence(),
+            // a negative literal is printed with its sign, which binds like unary minus
+            Expr::Value(Value::Number(number, negated)) if *negated || number.starts_with('-') => {
+                UnaryOperator::Minus.precedence()
+            }
+            Expr::Value(_)
+            | Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Nested(_)
+            | Expr::Function(_)
+            | Expr::Subquery(_)
+            | Expr::Exists { negated: false, .. }
+            | Expr::Cast {
+                kind: CastKind::Cast,
+                ..
+            }
+            | Expr::TypedString { .. }
+            | Expr::Case { .. } => u8::MAX,
+            Expr::Cast {
+                kind: CastKind::DoubleColon,
+                ..
+            } => DOUBLE_COLON_PRECEDENCE,
+        }
+    }
+}
+
+impl From<i32> for Expr {
+    fn from(value: i32) -> Self {
+        Expr::Value(Value::Number(value.to_string(), false))
+    }
+}
+
+impl From<i64> for Expr {
+    fn from(value: i64) -> Self {
+        Expr::Value(Value::Number(value.to_string(), false))
+    }
+}
+
+impl From<f64> for Expr {
+    fn from(value: f64) -> Self {
+        if value.is_finite() {
+            return Expr::Value(Value::Number(value.to_string(), false));
+        }
+        // NaN and the infinities have no numeric literal, only a float8 spelling
+        let spelling = if value.is_nan() {
+            "NaN"
+        } else if value.is_sign_positive() {
+            "Infinity"
+        } else {
+            "-Infinity"
+        };
+        Expr::Cast {
+            kind: CastKind::DoubleColon,
+            expr: Box::new(Expr::Value(Value::SingleQuotedString(spelling.to_string()))),
+            data_type: DataType::Custom(ObjectName(vec![Ident::new("float8")])),
+        }
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(value: &str) -> Self {
+        Expr::Value(Value::SingleQuotedString(value.to_string()))
+    }
+}
+
+impl From<String> for Expr {
+    fn from(value: String) -> Self {
+        Expr::Value(Value::SingleQuotedString(value))
+    }
+}
+
+impl From<bool> for Expr {
+    fn from(value: bool) -> Self {
+        Expr::Value(Value::Boolean(value))
+    }
+}
+
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        Expr::Value(value)
+    }
 }
 
 impl Display for SelectItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            SelectItem::UnnamedExpr(expr) => write!(f, "{}", expr),
+            SelectItem::ExprWithAlias { expr, alias } => write!(f, "{} AS {}", expr, alias),
+            SelectItem::QualifiedWildcard(prefix) => write!(f, "{}.*", prefix),
             SelectItem::Wildcard => write!(f, "*"),
         }
     }
 }
 
+impl Display for Returning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RETURNING {}", display_comma_separated(&self.0))
+    }
+}
+
 impl Display for QueryStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(with) = &self.with {
+            write!(f, "{} ", with)?;
+        }
         write!(f, "{}", self.body)?;
-        if let Some(order_by) = &self.order_by {
-            write!(f, " ORDER BY {}", order_by)?;
+        if !self.order_by.is_empty() {
+            write!(f, " ORDER BY {}", display_comma_separated(&self.order_by))?;
         }
         if let Some(limit) = &self.limit {
             write!(f, " LIMIT {}", limit)?;
         }
+        if let Some(offset) = &self.offset {
+            write!(f, " {}", offset)?;
+        }
+        if let Some(fetch) = &self.fetch {
+            write!(f, " {}", fetch)?;
+        }
+        for lock in &self.locks {
+            write!(f, " {}", lock)?;
+        }
         Ok(())
     }
 }
@@ -139,6 +731,27 @@ impl Display for SetExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SetExpr::Select(select) => write!(f, "{}", select),
+            SetExpr::Query(query) => write!(f, "({})", query),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                // set operations are left-associative, so a right operand of
+                // the same precedence needs parentheses to keep its grouping
+                if left.precedence() < op.precedence() {
+                    write!(f, "({})", left)?;
+                } else {
+                    write!(f, "{}", left)?;
+                }
+                write!(f, " {}{} ", op, if *all { " ALL" } else { "" })?;
+                if right.precedence() <= op.precedence() {
+                    write!(f, "({})", right)
+                } else {
+                    write!(f, "{}", right)
+                }
+            }
             SetExpr::Values(values) => write!(f, "{}", values),
         }
     }
@@ -155,12 +768,15 @@ impl Display for Select {
         if let Some(selection) = &self.selection {
             write!(f, " WHERE {}", selection)?;
         }
-        if let Some(group_by) = &self.group_by {
-            write!(f, " GROUP BY {}", group_by)?;
+        if !self.group_by.is_empty() {
+            write!(f, " GROUP BY {}", display_comma_separated(&self.group_by))?;
         }
         if let Some(having) = &self.having {
             write!(f, " HAVING {}", having)?;
         }
+        if !self.named_window.is_empty() {
+            write!(f, " WINDOW {}", display_comma_separated(&self.named_window))?;
+        }
         Ok(())
     }
 }
@@ -180,6 +796,277 @@ impl Display for Expr {
             }
             Expr::Value(value) => write!(f, "{}", value),
             Expr::Identifier(value) => write!(f, "{}", value),
+            Expr::CompoundIdentifier(idents) => write!(f, "{}", display_separated(idents, ".")),
+            Expr::BinaryOp { left, op, right } => write!(f, "{} {} {}", left, op, right),
+            Expr::UnaryOp { op, expr } => {
+                if op == &UnaryOperator::Not {
+                    write!(f, "{} {}", op, expr)
+                } else {
+                    write!(f, "{}{}", op, expr)
+                }
+            }
+            Expr::IsNull(expr) => write!(f, "{} IS NULL", expr),
+            Expr::IsNotNull(expr) => write!(f, "{} IS NOT NULL", expr),
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => write!(
+                f,
+                "{} {}BETWEEN {} AND {}",
+                expr,
+                if *negated { "NOT " } else { "" },
+                low,
+                high
+            ),
+            Expr::Like {
+                negated,
+                expr,
+                pattern,
+            } => write!(
+                f,
+                "{} {}LIKE {}",
+                expr,
+                if *negated { "NOT " } else { "" },
+                pattern
+            ),
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+            } => write!(
+                f,
+                "{} {}ILIKE {}",
+                expr,
+                if *negated { "NOT " } else { "" },
+                pattern
+            ),
+            Expr::Nested(expr) => write!(f, "({})", expr),
+            Expr::Function(function) => write!(f, "{}", function),
+            Expr::Subquery(subquery) => write!(f, "({})", subquery),
+            Expr::Exists { subquery, negated } => write!(
+                f,
+                "{}EXISTS ({})",
+                if *negated { "NOT " } else { "" },
+                subquery
+            ),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => write!(
+                f,
+                "{} {}IN ({})",
+                expr,
+                if *negated { "NOT " } else { "" },
+                subquery
+            ),
+            Expr::Cast {
+                kind: CastKind::Cast,
+                expr,
+                data_type,
+            } => write!(f, "CAST({} AS {})", expr, data_type),
+            Expr::Cast {
+                kind: CastKind::DoubleColon,
+                expr,
+                data_type,
+            } => write!(f, "{}::{}", expr, data_type),
+            Expr::TypedString { data_type, value } => write!(
+                f,
+                "{} {}",
+                data_type,
+                Value::SingleQuotedString(value.to_string())
+            ),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                write!(f, "CASE")?;
+                if let Some(operand) = operand {
+                    write!(f, " {}", operand)?;
+                }
+                for (condition, result) in conditions.iter().zip(results) {
+                    write!(f, " WHEN {} THEN {}", condition, result)?;
+                }
+                if let Some(else_result) = else_result {
+                    write!(f, " ELSE {}", else_result)?;
+                }
+                write!(f, " END")
+            }
+        }
+    }
+}
+
+/// A function call, aggregates take the optional DISTINCT and FILTER clauses
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function {
+    pub name: ObjectName,
+    pub args: Vec<FunctionArg>,
+    /// `DISTINCT` before the arguments, aggregates only
+    pub distinct: bool,
+    /// `FILTER (WHERE ...)`, aggregates only
+    pub filter: Option<Box<Expr>>,
+    /// `OVER (...)` or `OVER name`, makes the call a window function
+    pub over: Option<WindowType>,
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({}{})",
+            self.name,
+            if self.distinct { "DISTINCT " } else { "" },
+            display_comma_separated(&self.args)
+        )?;
+        if let Some(filter) = &self.filter {
+            write!(f, " FILTER (WHERE {})", filter)?;
+        }
+        if let Some(over) = &self.over {
+            write!(f, " OVER {}", over)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WindowType {
+    /// `OVER (PARTITION BY ... ORDER BY ... frame)`
+    WindowSpec(WindowSpec),
+    /// `OVER name`, refers to a window of the WINDOW clause
+    NamedWindow(Ident),
+}
+
+impl Display for WindowType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowType::WindowSpec(spec) => write!(f, "({})", spec),
+            WindowType::NamedWindow(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// The rows a window function sees, rendered without the surrounding parentheses
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub window_frame: Option<WindowFrame>,
+}
+
+impl Display for WindowSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut delim = "";
+        if !self.partition_by.is_empty() {
+            write!(
+                f,
+                "PARTITION BY {}",
+                display_comma_separated(&self.partition_by)
+            )?;
+            delim = " ";
+        }
+        if !self.order_by.is_empty() {
+            write!(
+                f,
+                "{}ORDER BY {}",
+                delim,
+                display_comma_separated(&self.order_by)
+            )?;
+            delim = " ";
+        }
+        if let Some(window_frame) = &self.window_frame {
+            write!(f, "{}{}", delim, window_frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// `{ ROWS | RANGE | GROUPS } [ BETWEEN <start> AND <end> | <start> ]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    /// Without an end bound the frame ends at the current row
+    pub end_bound: Option<WindowFrameBound>,
+}
+
+impl Display for WindowFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.end_bound {
+            Some(end_bound) => write!(
+                f,
+                "{} BETWEEN {} AND {}",
+                self.units, self.start_bound, end_bound
+            ),
+            None => write!(f, "{} {}", self.units, self.start_bound),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+impl Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+            WindowFrameUnits::Groups => "GROUPS",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WindowFrameBound {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<Box<Expr>>),
+    /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<Box<Expr>>),
+}
+
+impl Display for WindowFrameBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{} PRECEDING", n),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{} FOLLOWING", n),
+        }
+    }
+}
+
+/// `name AS (spec)` in the WINDOW clause of a SELECT
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedWindowDefinition(pub Ident, pub WindowSpec);
+
+impl Display for NamedWindowDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} AS ({})", self.0, self.1)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum FunctionArg {
+    Unnamed(Expr),
+    /// `*`, as in `count(*)`
+    Wildcard,
+}
+
+impl Display for FunctionArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FunctionArg::Unnamed(expr) => write!(f, "{}", expr),
+            FunctionArg::Wildcard => write!(f, "*"),
         }
     }
 }
@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use crate::query::Ident;
+
+/// `CREATE SCHEMA [ IF NOT EXISTS ] <name>`
+#[derive(Debug, PartialEq, Clone)]
+pub struct CreateSchemaStmt {
+    /// Skips an existing schema instead of failing
+    pub if_not_exists: bool,
+    pub name: Ident,
+}
+
+impl Display for CreateSchemaStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CREATE SCHEMA {}{}",
+            if self.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            self.name
+        )
+    }
+}
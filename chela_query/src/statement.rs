@@ -1,21 +1,46 @@
 use std::fmt::{Display, Formatter};
 
-use crate::{create::CreateStmt, insert::InsertStmt, query::QueryStmt};
+use crate::{
+    alter::AlterTableStmt, create::CreateStmt, delete::DeleteStmt, display::display_separated,
+    drop::DropStmt, index::CreateIndexStmt, insert::InsertStmt, query::QueryStmt,
+    schema::CreateSchemaStmt, truncate::TruncateStmt, update::UpdateStmt,
+};
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq)]
 pub enum Statement {
     CreateStmt(CreateStmt),
     QueryStmt(QueryStmt),
     InsertStmt(InsertStmt),
-    //TODO: update statement
+    UpdateStmt(UpdateStmt),
+    DeleteStmt(DeleteStmt),
+    AlterTableStmt(AlterTableStmt),
+    DropStmt(DropStmt),
+    TruncateStmt(TruncateStmt),
+    CreateIndexStmt(CreateIndexStmt),
+    CreateSchemaStmt(CreateSchemaStmt),
+}
+
+impl Statement {
+    /// Renders the statements as a script, one terminated statement per line
+    pub fn to_sql_batch(statements: &[Statement]) -> String {
+        display_separated(statements, "\n").to_string()
+    }
 }
 
 impl Display for Statement {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Statement::CreateStmt(create_stmt) => write!(f, "{};", create_stmt),
-            Statement::QueryStmt(_) => todo!(),
-            Statement::InsertStmt(_) => todo!(),
+            Statement::QueryStmt(query_stmt) => write!(f, "{};", query_stmt),
+            Statement::InsertStmt(insert_stmt) => write!(f, "{};", insert_stmt),
+            Statement::UpdateStmt(update_stmt) => write!(f, "{};", update_stmt),
+            Statement::DeleteStmt(delete_stmt) => write!(f, "{};", delete_stmt),
+            Statement::AlterTableStmt(alter_table_stmt) => write!(f, "{};", alter_table_stmt),
+            Statement::DropStmt(drop_stmt) => write!(f, "{};", drop_stmt),
+            Statement::TruncateStmt(truncate_stmt) => write!(f, "{};", truncate_stmt),
+            Statement::CreateIndexStmt(create_index_stmt) => write!(f, "{};", create_index_stmt),
+            Statement::CreateSchemaStmt(create_schema_stmt) => write!(f, "{};", create_schema_stmt),
         }
     }
 }
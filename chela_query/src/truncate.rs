@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use crate::{display::display_comma_separated, drop::DropBehavior, query::ObjectName};
+
+/// `TRUNCATE <name>, ... [ RESTART IDENTITY ] [ CASCADE | RESTRICT ]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TruncateStmt {
+    pub table_names: Vec<ObjectName>,
+    /// Resets the sequences owned by the tables, SERIAL ids start over at 1
+    pub restart_identity: bool,
+    /// CASCADE also empties the tables referencing these through foreign keys
+    pub behavior: Option<DropBehavior>,
+}
+
+impl Display for TruncateStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TRUNCATE {}", display_comma_separated(&self.table_names))?;
+        if self.restart_identity {
+            write!(f, " RESTART IDENTITY")?;
+        }
+        if let Some(behavior) = &self.behavior {
+            write!(f, " {}", behavior)?;
+        }
+        Ok(())
+    }
+}
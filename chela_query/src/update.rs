@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use crate::{
+    display::display_comma_separated,
+    query::{Expr, Ident, ObjectName, Returning},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpdateStmt {
+    /// TABLE
+    pub table_name: ObjectName,
+    /// Column assignments
+    pub assignments: Vec<Assignment>,
+    /// WHERE
+    pub selection: Option<Expr>,
+    /// RETURNING
+    pub returning: Option<Returning>,
+}
+
+impl Display for UpdateStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UPDATE {} SET {}",
+            self.table_name,
+            display_comma_separated(&self.assignments)
+        )?;
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {}", selection)?;
+        }
+        if let Some(returning) = &self.returning {
+            write!(f, " {}", returning)?;
+        }
+        Ok(())
+    }
+}
+
+/// SQL assignment `foo = expr` as used in the SET clause of an UPDATE
+#[derive(Debug, PartialEq, Clone)]
+pub struct Assignment {
+    pub id: Ident,
+    pub value: Expr,
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.id, self.value)
+    }
+}
@@ -1,17 +1,42 @@
 use std::fmt::Display;
 
-use crate::query::Expr;
+use crate::{
+    display::{dollar_quote_tag, escape_escaped_string, escape_single_quote_string},
+    params::BoundParam,
+    query::Expr,
+};
 
-#[derive(Debug, PartialEq,Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Value {
+    /// `'string value'`, rendered as an `E'...'` literal when it holds backslashes
     SingleQuotedString(String),
+    /// `$tag$string value$tag$`, with a tag that does not occur in the value
+    DollarQuotedString(String),
     Number(String, bool),
+    /// Value bound in the statement, rendered as its `$n` placeholder
+    Param(BoundParam),
+    /// `TRUE` or `FALSE`
+    Boolean(bool),
+    /// `NULL`
+    Null,
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Value::SingleQuotedString(s) => write!(f, "'{}'", s),
+            Value::SingleQuotedString(s) => {
+                // backslashes are only read as escapes inside E'' literals,
+                // whatever standard_conforming_strings is set to
+                if s.contains('\\') {
+                    write!(f, "E'{}'", escape_escaped_string(s))
+                } else {
+                    write!(f, "'{}'", escape_single_quote_string(s))
+                }
+            }
+            Value::DollarQuotedString(s) => {
+                let tag = dollar_quote_tag(s);
+                write!(f, "${}${}${}$", tag, s, tag)
+            }
             Value::Number(s, negated) => {
                 if *negated {
                     write!(f, "-{}", s)
@@ -19,17 +44,24 @@ impl Display for Value {
                     write!(f, "{}", s)
                 }
             }
+            Value::Param(param) => write!(f, "${}", param.index()),
+            Value::Boolean(true) => write!(f, "TRUE"),
+            Value::Boolean(false) => write!(f, "FALSE"),
+            Value::Null => write!(f, "NULL"),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Values(pub Vec<Vec<Expr>>);
 
 impl Display for Values {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "VALUES ")?;
-        for row in &self.0 {
+        for (i, row) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
             write!(f, "(")?;
             for (i, expr) in row.iter().enumerate() {
                 if i > 0 {
@@ -42,3 +74,86 @@ impl Display for Values {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+
+    /// Reads back the string a rendered literal stands for, the way Postgres would
+    fn parse_literal(sql: &str) -> String {
+        if let Some(body) = sql.strip_prefix('$') {
+            let tag_end = body.find('$').unwrap();
+            let delimiter = format!("${}", &body[..=tag_end]);
+            let body = &body[tag_end + 1..];
+            let end = body.find(&delimiter).unwrap();
+            assert_eq!(&body[end..], delimiter, "trailing input after {}", sql);
+            return body[..end].to_string();
+        }
+        let (escaped, body) = match sql.strip_prefix("E'") {
+            Some(body) => (true, body),
+            None => (false, sql.strip_prefix('\'').unwrap()),
+        };
+        let mut value = String::new();
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if escaped => value.push(chars.next().unwrap()),
+                '\'' => match chars.next() {
+                    Some('\'') => value.push('\''),
+                    None => return value,
+                    Some(c) => panic!("unescaped quote before {:?} in {}", c, sql),
+                },
+                c => value.push(c),
+            }
+        }
+        panic!("unterminated literal {}", sql)
+    }
+
+    #[test]
+    fn string_literal_escaping_test() {
+        let cases = [
+            ("it's", "'it''s'"),
+            ("C:\\temp", "E'C:\\\\temp'"),
+            ("'; DROP TABLE users; --", "'''; DROP TABLE users; --'"),
+        ];
+        for (value, sql) in cases {
+            assert_eq!(
+                Value::SingleQuotedString(value.to_string()).to_string(),
+                sql
+            );
+        }
+        assert_eq!(
+            Value::DollarQuotedString("it's".to_string()).to_string(),
+            "$$it's$$"
+        );
+        assert_eq!(
+            Value::DollarQuotedString("cost: $$5".to_string()).to_string(),
+            "$q1$cost: $$5$q1$"
+        );
+    }
+
+    #[test]
+    fn string_literal_round_trip_test() {
+        const ALPHABET: [char; 12] = [
+            'a', 'q', '1', ' ', '\'', '\\', '$', '"', '\n', 'é', '😀', 'E',
+        ];
+        // xorshift, so the run is reproducible without extra dependencies
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..10_000 {
+            let len = (next() % 24) as usize;
+            let value: String = (0..len)
+                .map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize])
+                .collect();
+            let single_quoted = Value::SingleQuotedString(value.clone()).to_string();
+            assert_eq!(parse_literal(&single_quoted), value, "{}", single_quoted);
+            let dollar_quoted = Value::DollarQuotedString(value.clone()).to_string();
+            assert_eq!(parse_literal(&dollar_quoted), value, "{}", dollar_quoted);
+        }
+    }
+}
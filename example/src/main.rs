@@ -4,12 +4,13 @@ use async_trait::async_trait;
 // use chela::Schema;
 use chela::*;
 use chela_query::builder::{
-    insert_into, not_null, primary_key_unique, select_table, serial, InsertBuilder, QueryBuilder,
+    insert_into, not_null, primary_key_unique, select_items, serial, InsertBuilder, QueryBuilder,
 };
 
 // use chela_query::create::Column;
 // use chela_query::create::ColumnType;
 use chela_query::create::DataType;
+use chela_query::params::CollectParams;
 use itertools::Itertools;
 use std::collections::HashMap;
 
@@ -30,11 +31,49 @@ struct User {
 struct Order {
     #[primary_key(auto_increment = true)]
     id: i32,
-    #[belongs_to(foreign_key = "id", table_name = "users")]
+    #[belongs_to(foreign_key = "id", table_name = "users", on_delete = "cascade")]
+    #[index]
     user_id: i32,
     price: f64,
 }
 
+#[allow(dead_code)]
+#[derive(ToEntity, PartialEq, Debug)]
+#[chela(
+    schema = "billing",
+    index(columns = "id, amount", unique, name = "invoice_amount")
+)]
+struct Invoice {
+    #[primary_key(auto_increment = true)]
+    id: i32,
+    amount: f64,
+}
+
+#[allow(dead_code)]
+#[derive(ToEntity, PartialEq, Debug)]
+struct Payment {
+    #[primary_key(auto_increment = true)]
+    id: i32,
+    #[belongs_to(foreign_key = "id", table_name = "invoices", schema = "billing")]
+    invoice_id: i32,
+}
+
+#[allow(dead_code)]
+#[derive(ToEntity, PartialEq, Debug)]
+struct Visit {
+    path: String,
+}
+
+#[allow(dead_code)]
+#[derive(ToEntity, PartialEq, Debug)]
+struct OrderLine {
+    #[primary_key]
+    order_id: i32,
+    #[primary_key]
+    line: i32,
+    quantity: i32,
+}
+
 impl<'a> PreloadBuilder<'a> for UserRepository {
     fn preload(&'a self, table_name: &'a str) -> &'a QueryBuilder {
         &self.preloads[table_name]
@@ -52,25 +91,43 @@ struct UserNew {
     username: String,
 }
 
+#[allow(dead_code)]
 struct OrderUser {
     id: i32,
     user: User,
     price: f64,
 }
 
+impl UserRepository {
+    /// INSERT returning the columns of the parent row, id included
+    fn create_statement(&self, input: UserNew) -> InsertBuilder {
+        let columns = self
+            .entity
+            .columns
+            .iter()
+            .map(|column| column.name.as_str());
+        self.insert()
+            .bind_values(vec![Box::new(input.username)])
+            .returning(select_items(columns))
+    }
+}
+
 fn main() {
     let repository = UserRepository::new();
-    repository.create(UserNew {
-        username: "John".to_string(),
-    });
+    let statement = repository
+        .create_statement(UserNew {
+            username: "John".to_string(),
+        })
+        .build();
+    println!("{} {:?}", statement, statement.params());
     let preload_query = repository
         .preload("orders")
         .clone()
         .in_list(vec![1, 2, 3, 4])
         .build();
-    println!("{}", preload_query.to_string());
+    println!("{}", preload_query);
 
-    let mut chela = Chela::new(vec![User::to_entity(), Order::to_entity()]); //Schema::new(vec![Box::new(point)]);
+    let chela = Chela::new(vec![User::to_entity(), Order::to_entity()]); //Schema::new(vec![Box::new(point)]);
     println!("{}", chela.migrations());
 }
 
@@ -90,8 +147,7 @@ fn main() {
 
 impl Builder for UserRepository {
     fn select(&self) -> QueryBuilder {
-        let query = select_table(self.entity.table_name.to_string());
-        query
+        self.entity.select()
     }
 
     fn insert(&self) -> InsertBuilder {
@@ -103,8 +159,8 @@ impl Builder for UserRepository {
             .filter(|column| !column.is_primary())
             .map(|column| column.name.to_string())
             .collect();
-        let query = insert_into(self.entity.table_name.to_string()).columns(column_names);
-        query
+
+        insert_into(self.entity.table_name.to_string()).columns(column_names)
     }
 }
 
@@ -114,21 +170,25 @@ impl QueryRunner for UserRepository {
 
     type CreateInput = UserNew;
 
-    fn create(&self, input: UserNew) {
-        //client: &Client
-        // let values = self.entity.columns.into_iter().map(|column| column.name.to_string());
-        let statement = self.insert().values(vec![input.username]).build();
-        println!("{}", &statement.to_string());
+    async fn create(&self, client: &Client, input: UserNew) -> User {
+        let statement = self.create_statement(input).build();
+        let row = client
+            .query_one(&statement.to_string(), &statement.params())
+            .await
+            .unwrap();
+        let user = UserOuter::from(row);
+        User {
+            id: user.id,
+            username: user.username,
+            orders: vec![],
+        }
     }
     async fn load(&self, client: &Client) -> Vec<User> {
         let entity = self.entity();
         let parent_query = self.select().build();
 
         let parent_rows = client.query(&parent_query.to_string(), &[]).await.unwrap();
-        let users_outer: Vec<UserOuter> = parent_rows
-            .into_iter()
-            .map(|row| UserOuter::from(row))
-            .collect();
+        let users_outer: Vec<UserOuter> = parent_rows.into_iter().map(UserOuter::from).collect();
         let ids = users_outer.iter().map(|user| user.id).collect::<Vec<_>>();
         let parent_and_ids = users_outer
             .iter()
@@ -138,29 +198,27 @@ impl QueryRunner for UserRepository {
         let many_row0_query = self
             .preload(&entity.has_many[0].table_name.to_string())
             .clone()
-            .in_list(ids)
+            .bind_in_list(ids)
             .build();
 
         let many_row0 = client
-            .query(&many_row0_query.to_string(), &[])
+            .query(&many_row0_query.to_string(), &many_row0_query.params())
             .await
             .unwrap();
-        let many_row0result: Vec<Order> =
-            many_row0.into_iter().map(|row| Order::from(row)).collect();
+        let many_row0result: Vec<Order> = many_row0.into_iter().map(Order::from).collect();
 
         let lookup_many0result = many_row0result
             .into_iter()
             .into_group_map_by(|order| order.id);
 
-        let result = parent_and_ids
+        parent_and_ids
             .into_iter()
             .map(|(user, id)| User {
                 orders: lookup_many0result[&id].to_owned(),
                 id: user.id,
                 username: user.username.to_owned(),
             })
-            .collect::<Vec<_>>();
-        result
+            .collect::<Vec<_>>()
     }
 }
 
@@ -185,9 +243,12 @@ impl From<tokio_postgres::row::Row> for Order {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Order, OrderUser, User, UserOuter};
+    use crate::{Invoice, Order, OrderLine, Payment, User, UserOuter, Visit};
+    use chela::migrator::Migrator;
+    use chela::{Chela, ToEntity};
+    use chela_query::alter::AlterColumnOperation;
+    use chela_query::params::CollectParams;
     use itertools::Itertools;
-    use std::collections::HashMap;
 
     // #[test]
     // fn preload_belongs_to() {
@@ -228,6 +289,150 @@ mod tests {
     //     assert_eq!(result, expected_users);
     // }
 
+    #[test]
+    fn entity_schema_works() {
+        let entity = Invoice::to_entity();
+        assert_eq!(
+            entity.clone().create_table().to_string(),
+            "CREATE TABLE IF NOT EXISTS billing.invoices (id SERIAL PRIMARY KEY, amount DOUBLE NOT NULL)"
+        );
+        assert_eq!(
+            entity.select().build().to_string(),
+            "SELECT id, amount FROM billing.invoices"
+        );
+        assert_eq!(
+            Payment::to_entity().create_table().to_string(),
+            "CREATE TABLE IF NOT EXISTS payments (id SERIAL PRIMARY KEY, invoice_id INT NOT NULL, \
+             CONSTRAINT fk_invoice FOREIGN KEY (invoice_id) REFERENCES billing.invoices (id))"
+        );
+    }
+
+    #[test]
+    fn entity_composite_primary_key_works() {
+        let entity = OrderLine::to_entity();
+        assert_eq!(
+            entity.clone().create_table().to_string(),
+            "CREATE TABLE IF NOT EXISTS orderlines (order_id INT, line INT, quantity INT NOT NULL, \
+             PRIMARY KEY (order_id, line))"
+        );
+        assert_eq!(
+            entity
+                .upsert()
+                .bind_row(vec![Box::new(1), Box::new(1), Box::new(3)])
+                .build()
+                .to_string(),
+            "INSERT INTO orderlines (order_id, line, quantity) VALUES ($1, $2, $3) \
+             ON CONFLICT (order_id, line) DO UPDATE SET quantity = excluded.quantity"
+        );
+    }
+
+    #[test]
+    fn entity_alter_table_works() {
+        let invoice = Invoice::to_entity();
+        let amount = invoice.columns[1].clone();
+        assert_eq!(
+            invoice.add_column(amount.clone()).to_string(),
+            "ALTER TABLE billing.invoices ADD COLUMN amount DOUBLE NOT NULL"
+        );
+        assert_eq!(
+            invoice
+                .alter_column(
+                    "amount",
+                    vec![
+                        AlterColumnOperation::SetDataType {
+                            data_type: amount.data_type,
+                            using: None,
+                        },
+                        AlterColumnOperation::SetNotNull,
+                    ],
+                )
+                .to_string(),
+            "ALTER TABLE billing.invoices ALTER COLUMN amount SET DATA TYPE DOUBLE, \
+             ALTER COLUMN amount SET NOT NULL"
+        );
+        assert_eq!(
+            invoice.rename_column("amount", "total").to_string(),
+            "ALTER TABLE billing.invoices RENAME COLUMN amount TO total"
+        );
+        assert_eq!(
+            invoice.drop_column("total").to_string(),
+            "ALTER TABLE billing.invoices DROP COLUMN total"
+        );
+
+        let order = Order::to_entity();
+        assert_eq!(
+            order.create_constraint(&order.belongs_to[0]).to_string(),
+            "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE"
+        );
+        assert_eq!(
+            order.drop_constraint("fk_user").to_string(),
+            "ALTER TABLE orders DROP CONSTRAINT fk_user"
+        );
+    }
+
+    #[test]
+    fn teardown_works() {
+        let chela = Chela::new(vec![
+            User::to_entity(),
+            Order::to_entity(),
+            Invoice::to_entity(),
+        ]);
+        assert_eq!(
+            chela.teardown().to_string(),
+            "DROP TABLE IF EXISTS billing.invoices;\n\
+             DROP TABLE IF EXISTS orders;\n\
+             DROP TABLE IF EXISTS users;"
+        );
+    }
+
+    #[test]
+    fn entity_indexes_work() {
+        let chela = Chela::new(vec![
+            User::to_entity(),
+            Order::to_entity(),
+            Invoice::to_entity(),
+        ]);
+        assert_eq!(
+            chela.migrations().to_string(),
+            "CREATE SCHEMA IF NOT EXISTS billing;\n\
+             CREATE TABLE IF NOT EXISTS users (id SERIAL PRIMARY KEY, username VARCHAR(255) NOT NULL);\n\
+             CREATE TABLE IF NOT EXISTS orders (id SERIAL PRIMARY KEY, user_id INT NOT NULL, \
+             price DOUBLE NOT NULL, CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE);\n\
+             CREATE TABLE IF NOT EXISTS billing.invoices (id SERIAL PRIMARY KEY, amount DOUBLE NOT NULL);\n\
+             CREATE INDEX IF NOT EXISTS orders_user_id_idx ON orders (user_id);\n\
+             CREATE UNIQUE INDEX IF NOT EXISTS invoice_amount ON billing.invoices (id, amount);"
+        );
+        assert_eq!(
+            Invoice::to_entity()
+                .drop_index("invoice_amount")
+                .to_string(),
+            "DROP INDEX IF EXISTS billing.invoice_amount"
+        );
+    }
+
+    #[test]
+    fn entity_upsert_works() {
+        let insert = Invoice::to_entity()
+            .upsert()
+            .bind_row(vec![Box::new(1), Box::new(9.5)])
+            .build();
+        assert_eq!(
+            insert.to_string(),
+            "INSERT INTO billing.invoices (id, amount) VALUES ($1, $2) \
+             ON CONFLICT (id) DO UPDATE SET amount = excluded.amount"
+        );
+        assert_eq!(insert.params().len(), 2);
+
+        let insert = Visit::to_entity()
+            .upsert()
+            .bind_row(vec![Box::new("/")])
+            .build();
+        assert_eq!(
+            insert.to_string(),
+            "INSERT INTO visits (path) VALUES ($1) ON CONFLICT DO NOTHING"
+        );
+    }
+
     #[test]
     fn preload_has_many_works() {
         let expected_users = vec![User {
@@ -239,7 +444,7 @@ mod tests {
                 price: 200.0,
             }],
         }];
-        let users_outer = vec![UserOuter {
+        let users_outer = [UserOuter {
             id: 1,
             username: "origin".to_string(),
         }];